@@ -1,4 +1,6 @@
 use super::{Bip38Error, Result};
+use crate::progress::{Progress, Tracker};
+use crate::secret::protect;
 use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit, generic_array::GenericArray};
 use bitcoin::secp256k1::Secp256k1;
 use bitcoin::{Address, Network, NetworkKind, PrivateKey, PublicKey, base58};
@@ -12,16 +14,17 @@ const PRE_NON_EC: [u8; 2] = [0x01, 0x42];
 const PRE_EC: [u8; 2] = [0x01, 0x43];
 
 pub trait NoneEc {
-    fn encrypt_non_ec(wif: &str, passphrase: &str) -> Result<String> {
+    fn encrypt_non_ec(wif: &str, passphrase: &str, progress: Progress) -> Result<String> {
         let prvk = PrivateKey::from_wif(wif)?;
         let compress = prvk.compressed;
         let salt = prvk.p2pkh()?.as_bytes().sha256_n(2)[0..4].to_vec();
 
-        let mut scrypt_key = [0u8; 64];
+        let mut scrypt_key = protect([0u8; 64]);
         {
-            let pass = passphrase.nfc().collect::<String>();
+            let pass = protect(passphrase.nfc().collect::<String>());
             let params = scrypt::Params::new(14, 8, 8, 64)?;
-            scrypt::scrypt(pass.as_bytes(), &salt, &params, &mut scrypt_key)?;
+            let mut tracker = Tracker::new(progress, Tracker::scrypt_units(&params));
+            tracker.scrypt(pass.as_bytes(), &salt, &params, &mut scrypt_key[..])?;
         }
 
         let (ref part1, ref part2) = {
@@ -48,7 +51,7 @@ pub trait NoneEc {
         Ok(base58::encode_check(&buffer))
     }
 
-    fn decrypt_non_ec(wif: &str, passphrase: &str) -> Result<String> {
+    fn decrypt_non_ec(wif: &str, passphrase: &str, progress: Progress) -> Result<String> {
         let mut ebuffer = base58::decode_check(wif)?;
         if ebuffer.len() != 39 || ebuffer[..2] != PRE_NON_EC {
             return Err(Bip38Error::InvalidKey);
@@ -56,11 +59,12 @@ pub trait NoneEc {
         let [ref flag, ref salt, epart1, epart2] = ebuffer[2..].segments_mut([1, 4, 16, 16]);
         let compress = flag[0] & 0x20 == 0x20;
 
-        let mut scrypt_key = [0u8; 64];
+        let mut scrypt_key = protect([0u8; 64]);
         {
-            let pass = passphrase.nfc().collect::<String>();
+            let pass = protect(passphrase.nfc().collect::<String>());
             let params = scrypt::Params::new(14, 8, 8, 64)?;
-            scrypt::scrypt(pass.as_bytes(), salt, &params, &mut scrypt_key)?;
+            let mut tracker = Tracker::new(progress, Tracker::scrypt_units(&params));
+            tracker.scrypt(pass.as_bytes(), salt, &params, &mut scrypt_key[..])?;
         };
 
         // Decrypt the two parts of the key
@@ -92,17 +96,24 @@ pub trait EcMultiply {
     /// EC_PASS not has "lot" and "sequence".
     const PRE_EC_PASS_NON: [u8; 8] = [0x2C, 0xE9, 0xB3, 0xE1, 0xFF, 0x39, 0xE2, 0x53];
 
-    fn generate_ec_factor(passphrase: &str, salt: [u8; 8], lot: u32, seq: u32) -> Result<String> {
+    fn generate_ec_factor(
+        passphrase: &str,
+        salt: [u8; 8],
+        lot: u32,
+        seq: u32,
+        progress: Progress,
+    ) -> Result<String> {
+        let params = scrypt::Params::new(14, 8, 8, 32)?;
+        let mut tracker = Tracker::new(progress, Tracker::scrypt_units(&params));
         match (lot, seq) {
             (100000..=999999, 1..=4095) => {
                 let salt = salt[..4].to_vec();
                 let entropy = [&salt[..4], &(lot << 12 | seq).to_be_bytes()[..4]].concat();
 
                 let pass_factor = {
-                    let pass = passphrase.nfc().collect::<String>();
-                    let params = scrypt::Params::new(14, 8, 8, 32)?;
-                    let mut pre_factor = [0u8; 32];
-                    scrypt::scrypt(pass.as_bytes(), &salt, &params, &mut pre_factor)?;
+                    let pass = protect(passphrase.nfc().collect::<String>());
+                    let mut pre_factor = protect([0u8; 32]);
+                    tracker.scrypt(pass.as_bytes(), &salt, &params, &mut pre_factor[..])?;
 
                     [&pre_factor[..32], &entropy[..8]].concat().sha256_n(2)
                 };
@@ -122,13 +133,12 @@ pub trait EcMultiply {
             }
             (0, 0) => {
                 let entropy: [u8; 8] = salt;
-                let mut pass_factor = [0u8; 32];
+                let mut pass_factor = protect([0u8; 32]);
                 {
-                    let pass = passphrase.nfc().collect::<String>();
-                    let params = scrypt::Params::new(14, 8, 8, 32)?;
-                    scrypt::scrypt(pass.as_bytes(), &entropy, &params, &mut pass_factor)?;
+                    let pass = protect(passphrase.nfc().collect::<String>());
+                    tracker.scrypt(pass.as_bytes(), &entropy, &params, &mut pass_factor[..])?;
                 }
-                let pass_point = PrivateKey::from_slice(&pass_factor, NetworkKind::Main)?
+                let pass_point = PrivateKey::from_slice(&pass_factor[..], NetworkKind::Main)?
                     .public_key(&Secp256k1::default())
                     .to_bytes();
                 debug_assert_eq!(pass_point.len(), 33);
@@ -162,11 +172,11 @@ pub trait EcMultiply {
             pub_key.p2pkh()?.as_bytes().sha256_n(2)[0..4].to_vec()
         };
 
-        let mut scrypt_key = [0u8; 64];
+        let mut scrypt_key = protect([0u8; 64]);
         {
             let salt = [&address_hash[..4], &entropy[..8]].concat();
             let params = scrypt::Params::new(10, 1, 1, 64)?;
-            scrypt::scrypt(pass_point, &salt, &params, &mut scrypt_key)?;
+            scrypt::scrypt(pass_point, &salt, &params, &mut scrypt_key[..])?;
         };
 
         let (ref part1, ref part2) = {
@@ -196,7 +206,7 @@ pub trait EcMultiply {
         Ok(base58::encode_check(&result))
     }
 
-    fn decrypt_ec_key(wif_ec_key: &str, passphrase: &str) -> Result<String> {
+    fn decrypt_ec_key(wif_ec_key: &str, passphrase: &str, progress: Progress) -> Result<String> {
         let ebuffer = base58::decode_check(wif_ec_key)?;
         if ebuffer.len() != 39 || ebuffer[..2] != PRE_EC {
             return Err(Bip38Error::InvalidKey);
@@ -208,27 +218,28 @@ pub trait EcMultiply {
             false => &entropy[..8],
         };
 
-        let pass_factor: [u8; 32] = {
-            let mut pre_factor = [0u8; 32];
-            {
-                let pass = passphrase.nfc().collect::<String>();
-                let params = scrypt::Params::new(14, 8, 8, 64)?;
-                scrypt::scrypt(pass.as_bytes(), salt, &params, &mut pre_factor)?;
-            }
-            match lot_seq {
-                true => [&pre_factor[..32], &entropy[..8]].concat().sha256_n(2),
-                false => pre_factor,
+        let params = scrypt::Params::new(14, 8, 8, 64)?;
+        let seed_params = scrypt::Params::new(10, 1, 1, 64)?;
+        let total = Tracker::scrypt_units(&params) + Tracker::scrypt_units(&seed_params);
+        let mut tracker = Tracker::new(progress, total);
+
+        let mut pass_factor = protect([0u8; 32]);
+        {
+            let pass = protect(passphrase.nfc().collect::<String>());
+            tracker.scrypt(pass.as_bytes(), salt, &params, &mut pass_factor[..])?;
+            if lot_seq {
+                let factor = [&pass_factor[..32], &entropy[..8]].concat().sha256_n(2);
+                pass_factor.copy_from_slice(&factor);
             }
-        };
+        }
 
-        let mut seed = [0u8; 64];
+        let mut seed = protect([0u8; 64]);
         {
-            let pass_point = PrivateKey::from_slice(&pass_factor, Network::Bitcoin)?
+            let pass_point = PrivateKey::from_slice(&pass_factor[..], Network::Bitcoin)?
                 .public_key(&Secp256k1::default())
                 .to_bytes();
             let salt = [&address_hash[..4], &entropy[..8]].concat();
-            let params = scrypt::Params::new(10, 1, 1, 64)?;
-            scrypt::scrypt(&pass_point, &salt, &params, &mut seed)?;
+            tracker.scrypt(&pass_point, &salt, &seed_params, &mut seed[..])?;
         }
 
         let factor: [u8; 32] = {
@@ -247,7 +258,8 @@ pub trait EcMultiply {
         };
 
         // private key
-        let mut prvk = PrivateKey::from_slice(&pass_factor, Network::Bitcoin)?.mul_tweak(factor)?;
+        let mut prvk =
+            PrivateKey::from_slice(&pass_factor[..], Network::Bitcoin)?.mul_tweak(factor)?;
         prvk.compressed = compress;
 
         // checksum
@@ -270,11 +282,40 @@ pub trait Bip38: NoneEc + EcMultiply {
     /// Decrypts a BIP38 encrypted key using the provided passphrase.
     fn bip38_decrypt(&self, passphrase: &str) -> Result<String>;
 
+    /// Decrypts a BIP38 encrypted key to WIF of network.
+    /// Address hash of encrypted key is always mainnet, same as `bip38_decrypt`.
+    fn bip38_decrypt_in(&self, passphrase: &str, network: Network) -> Result<String>;
+
     /// Generates an EC factor for BIP38 encryption.
     fn bip38_ec_factor(&self, lot: u32, seq: u32) -> Result<String>;
 
     /// Generates an EC key for BIP38 encryption.
     fn bip38_ec_generate(&self) -> Result<String>;
+
+    /// Encrypts a WIF private key with progress callback.
+    /// `progress` receives (done, total) work units, returns `false` to cancel.
+    fn bip38_encrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String>;
+
+    /// Decrypts a BIP38 encrypted key with progress callback.
+    /// `progress` receives (done, total) work units, returns `false` to cancel.
+    fn bip38_decrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String>;
+
+    /// Generates an EC factor with progress callback.
+    /// `progress` receives (done, total) work units, returns `false` to cancel.
+    fn bip38_ec_factor_with_progress(
+        &self,
+        lot: u32,
+        seq: u32,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String>;
 }
 
 impl NoneEc for str {}
@@ -282,27 +323,25 @@ impl EcMultiply for str {}
 impl Bip38 for str {
     #[inline(always)]
     fn bip38_encrypt(&self, passphrase: &str) -> Result<String> {
-        Self::encrypt_non_ec(self, passphrase)
+        Self::encrypt_non_ec(self, passphrase, None)
     }
 
     #[inline(always)]
     fn bip38_decrypt(&self, passphrase: &str) -> Result<String> {
-        if self.starts_with("6P") && self.len() == 58 {
-            let pre = base58::decode_check(self)?[..2].to_vec();
-            if pre == PRE_NON_EC {
-                return Self::decrypt_non_ec(self, passphrase);
-            } else if pre == PRE_EC {
-                return Self::decrypt_ec_key(self, passphrase);
-            }
-        }
-        Err(Bip38Error::InvalidKey)
+        decrypt(self, passphrase, None)
+    }
+
+    fn bip38_decrypt_in(&self, passphrase: &str, network: Network) -> Result<String> {
+        let mut prvk = PrivateKey::from_wif(&self.bip38_decrypt(passphrase)?)?;
+        prvk.network = network.into();
+        Ok(prvk.to_wif())
     }
 
     #[inline]
     fn bip38_ec_factor(&self, lot: u32, seq: u32) -> Result<String> {
         let mut salt = [0u8; 8];
         rand::thread_rng().fill_bytes(&mut salt);
-        Self::generate_ec_factor(self, salt, lot, seq)
+        Self::generate_ec_factor(self, salt, lot, seq, None)
     }
 
     #[inline]
@@ -311,6 +350,48 @@ impl Bip38 for str {
         rand::thread_rng().fill_bytes(&mut seed);
         Self::generate_ec_key(seed, self)
     }
+
+    #[inline(always)]
+    fn bip38_encrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String> {
+        Self::encrypt_non_ec(self, passphrase, Some(progress))
+    }
+
+    #[inline(always)]
+    fn bip38_decrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String> {
+        decrypt(self, passphrase, Some(progress))
+    }
+
+    fn bip38_ec_factor_with_progress(
+        &self,
+        lot: u32,
+        seq: u32,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String> {
+        let mut salt = [0u8; 8];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::generate_ec_factor(self, salt, lot, seq, Some(progress))
+    }
+}
+
+/// Decrypt non ec or ec encrypted key
+fn decrypt(wif: &str, passphrase: &str, progress: Progress) -> Result<String> {
+    if wif.starts_with("6P") && wif.len() == 58 {
+        let pre = base58::decode_check(wif)?[..2].to_vec();
+        if pre == PRE_NON_EC {
+            return str::decrypt_non_ec(wif, passphrase, progress);
+        } else if pre == PRE_EC {
+            return str::decrypt_ec_key(wif, passphrase, progress);
+        }
+    }
+    Err(Bip38Error::InvalidKey)
 }
 
 trait ByteOperation {
@@ -440,14 +521,53 @@ mod tests {
         for data in TEST_DATA.chunks(3) {
             let (pwd, enc_wif, wif) = (data[0], data[1], data[2]);
 
-            let encrypted = str::encrypt_non_ec(wif, pwd).expect("Encryption failed");
+            let encrypted = str::encrypt_non_ec(wif, pwd, None).expect("Encryption failed");
             assert_eq!(encrypted, *enc_wif, "Encryption mismatch");
 
-            let decrypted = str::decrypt_non_ec(&encrypted, pwd).expect("Decryption failed");
+            let decrypted = str::decrypt_non_ec(&encrypted, pwd, None).expect("Decryption failed");
             assert_eq!(decrypted, *wif, "Decryption mismatch");
         }
     }
 
+    #[test]
+    fn test_decrypt_network() -> Result<()> {
+        const WIF: &str = "L44B5gGEpqEDRS9vVPz7QT35jcBG2r3CZwSwQ4fCewXAhAhqGVpP";
+        const ENC_WIF: &str = "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo";
+        let decrypted = ENC_WIF.bip38_decrypt_in("TestingOneTwoThree", Network::Testnet)?;
+        let (prvk, expected) = (
+            PrivateKey::from_wif(&decrypted)?,
+            PrivateKey::from_wif(WIF)?,
+        );
+        assert_eq!(prvk.network, NetworkKind::Test);
+        assert_eq!(
+            (prvk.inner, prvk.compressed),
+            (expected.inner, expected.compressed)
+        );
+
+        // testnet key is encrypted with mainnet address hash
+        assert_eq!(decrypted.bip38_encrypt("TestingOneTwoThree")?, ENC_WIF);
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress() -> Result<()> {
+        const WIF: &str = "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR";
+        const ENC_WIF: &str = "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg";
+        let mut last = (0, 0);
+        let encrypted =
+            WIF.bip38_encrypt_with_progress("TestingOneTwoThree", &mut |done, total| {
+                last = (done, total);
+                true
+            })?;
+        assert_eq!(encrypted, ENC_WIF);
+        assert_eq!(last, (1 << 21, 1 << 21));
+
+        let r = ENC_WIF
+            .bip38_decrypt_with_progress("TestingOneTwoThree", &mut |done, total| done < total / 2);
+        assert!(matches!(r, Err(Bip38Error::Cancelled)));
+        Ok(())
+    }
+
     #[test]
     fn test_ec_pass() -> std::result::Result<(), anyhow::Error> {
         const TEST_DATA: &[&str] = &[
@@ -493,7 +613,7 @@ mod tests {
             }
             println!("salt: {:x?}", &bs[8..16]);
 
-            let ec_pass = str::generate_ec_factor(pass, salt, lot, seq)?;
+            let ec_pass = str::generate_ec_factor(pass, salt, lot, seq, None)?;
             assert_eq!(ec_pass, factor);
         }
         Ok(())
@@ -1,5 +1,7 @@
 use super::{Bip38Error, Result};
 use crate::bip39::Mnemonic;
+use crate::progress::{Progress, Tracker};
+use crate::secret::{Secret, protect};
 use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit, generic_array::GenericArray};
 use rand::RngCore;
 use unicode_normalization::UnicodeNormalization;
@@ -104,21 +106,29 @@ impl std::str::FromStr for MnemonicEx {
 
 trait Derivation {
     /// Derive a secret key from the passphrase and salt.
-    fn derive_secret_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 64]> {
-        let pass: String = passphrase.nfc().collect();
+    fn derive_secret_key(
+        passphrase: &str,
+        salt: &[u8],
+        progress: Progress,
+    ) -> Result<Secret<[u8; 64]>> {
+        let pass = protect(passphrase.nfc().collect::<String>());
+        let params = scrypt::Params::new(20, 8, 8, 64)?;
+        let argon = argon2::Argon2::default();
+        let total = Tracker::scrypt_units(&params) + Tracker::argon2_units(argon.params());
+        let mut tracker = Tracker::new(progress, total);
+
         let argon_salt = {
             let scrypt_salt = [DEFAULT_SALT.as_bytes(), salt].concat();
-            let params = scrypt::Params::new(20, 8, 8, 64)?;
-            let mut result = [0u8; 64];
-            scrypt::scrypt(pass.as_bytes(), &scrypt_salt, &params, &mut result)?;
+            let mut result = protect([0u8; 64]);
+            tracker.scrypt(pass.as_bytes(), &scrypt_salt, &params, &mut result[..])?;
 
             let (half1, half2) = result.split_at_mut(32);
             half1[..32].xor(&half2[..32]);
-            half1[..32].to_vec()
+            protect(half1[..32].to_vec())
         };
-        let argon = argon2::Argon2::default();
-        let mut secret_key = [0u8; 64];
-        argon.hash_password_into(pass.as_bytes(), &argon_salt, &mut secret_key)?;
+        let mut secret_key = protect([0u8; 64]);
+        argon.hash_password_into(pass.as_bytes(), &argon_salt, &mut secret_key[..])?;
+        tracker.advance(Tracker::argon2_units(argon.params()))?;
         Ok(secret_key)
     }
 
@@ -129,13 +139,13 @@ trait Derivation {
         use pbkdf2::pbkdf2_hmac;
 
         let seed = {
-            let mnemonic = mnemonic.to_string();
+            let mnemonic = protect(mnemonic.to_string());
             let salt = format!("mnemonic{DEFAULT_SALT}").into_bytes();
-            let mut seed = [0u8; 64];
-            pbkdf2_hmac::<sha2::Sha512>(mnemonic.as_bytes(), &salt, u32::pow(2, 11), &mut seed);
+            let mut seed = protect([0u8; 64]);
+            pbkdf2_hmac::<sha2::Sha512>(mnemonic.as_bytes(), &salt, u32::pow(2, 11), &mut seed[..]);
             seed
         };
-        let root = Xpriv::new_master(Network::Bitcoin, &seed)?;
+        let root = Xpriv::new_master(Network::Bitcoin, &seed[..])?;
 
         let address = {
             let derive_path: DerivationPath = path.parse()?;
@@ -152,25 +162,25 @@ trait Encryption: Derivation + Sized {
     ///   returning the new mnemonic and a verify word.
     /// The salt is used to extend the mnemonic length,
     ///   and the verify word is used to verify the decryption.
-    fn encrypt_extend(&self, passphrase: &str, salt: &[u8]) -> Result<Self>;
+    fn encrypt_extend(&self, passphrase: &str, salt: &[u8], progress: Progress) -> Result<Self>;
 
     /// Decrypt the mnemonic with a passphrase and verify word, returning the original mnemonic.
     /// If the verify word is empty, it will ignore the checksum.
     /// The verify word can be a word from the mnemonic language
     ///   or a count in the format "12", "15", "18", "21", or "24".
-    fn decrypt_extend(&self, passphrase: &str) -> Result<Self>;
+    fn decrypt_extend(&self, passphrase: &str, progress: Progress) -> Result<Self>;
 }
 
 impl Derivation for MnemonicEx {}
 impl Encryption for MnemonicEx {
-    fn encrypt_extend(&self, passphrase: &str, salt: &[u8]) -> Result<Self> {
+    fn encrypt_extend(&self, passphrase: &str, salt: &[u8], progress: Progress) -> Result<Self> {
         let result_bytes = self.size() / 3 * 4 + salt.len();
         assert!(matches!(result_bytes, 16 | 20 | 24 | 28 | 32));
 
-        let secret_key = Self::derive_secret_key(passphrase, salt)?;
+        let secret_key = Self::derive_secret_key(passphrase, salt, progress)?;
         let (mask, aes_key) = secret_key.split_at(32);
 
-        let entropy = &mut self.entropy();
+        let entropy = &mut protect(self.entropy());
         {
             entropy.resize(32, 0);
             entropy[..32].xor(&mask[..32]);
@@ -198,14 +208,14 @@ impl Encryption for MnemonicEx {
         Ok(MnemonicEx { mnemonic, verify })
     }
 
-    fn decrypt_extend(&self, passphrase: &str) -> Result<Self> {
+    fn decrypt_extend(&self, passphrase: &str, progress: Progress) -> Result<Self> {
         let result_bytes = self.desired_size() / 3 * 4;
         assert!(matches!(result_bytes, 16 | 20 | 24 | 28 | 32));
 
-        let entropy = &mut self.entropy();
+        let entropy = &mut protect(self.entropy());
         {
             let salt: Vec<_> = entropy.drain(result_bytes..).collect();
-            let secret_key = Self::derive_secret_key(passphrase, &salt)?;
+            let secret_key = Self::derive_secret_key(passphrase, &salt, progress)?;
             let (mask, aes_key) = secret_key.split_at(32);
 
             entropy.resize(32, 0);
@@ -243,37 +253,85 @@ pub trait MnemonicEncryption {
     /// Decrypt the mnemonic with a passphrase.
     /// If the mnemonic is encrypted with a verify word, it will be used to verify the decryption.
     fn mnemonic_decrypt(&self, passphrase: &str) -> Result<String>;
+
+    /// Encrypt the mnemonic with progress callback.
+    /// `progress` receives (done, total) work units, returns `false` to cancel.
+    /// Argon2 is reported once when finished, it can't be cancelled while running.
+    fn mnemonic_encrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String>;
+
+    /// Decrypt the mnemonic with progress callback.
+    /// `progress` receives (done, total) work units, returns `false` to cancel.
+    /// Argon2 is reported once when finished, it can't be cancelled while running.
+    fn mnemonic_decrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String>;
 }
 impl MnemonicEncryption for str {
     /// Encrypt the mnemonic with a passphrase and desired word count.
+    #[inline]
     fn mnemonic_encrypt(&self, passphrase: &str) -> Result<String> {
-        let original: MnemonicEx = self.parse()?;
-        if original.desired_size() < original.size() {
-            return Err(Bip38Error::InvalidSize);
-        }
-
-        // Generate a random salt if the desired size is greater than the original.
-        // The salt will be used to extend the mnemonic length.
-        let salt = &mut vec![0u8; (original.desired_size() - original.size()) / 3 * 4];
-        if !salt.is_empty() {
-            rand::thread_rng().fill_bytes(salt);
-        }
-
-        let mnemonic = original.encrypt_extend(passphrase, salt)?;
-        Ok(mnemonic.to_string())
+        encrypt(self, passphrase, None)
     }
 
     /// Decrypt the mnemonic with a passphrase.
+    #[inline]
     fn mnemonic_decrypt(&self, passphrase: &str) -> Result<String> {
-        let mnemonic: MnemonicEx = self.parse()?;
-        if mnemonic.desired_size() > mnemonic.size() {
-            return Err(Bip38Error::InvalidSize);
-        }
-        let original = mnemonic.decrypt_extend(passphrase)?;
-        Ok(original.to_string())
+        decrypt(self, passphrase, None)
+    }
+
+    #[inline]
+    fn mnemonic_encrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String> {
+        encrypt(self, passphrase, Some(progress))
+    }
+
+    #[inline]
+    fn mnemonic_decrypt_with_progress(
+        &self,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<String> {
+        decrypt(self, passphrase, Some(progress))
     }
 }
 
+/// Encrypt mnemonic, extended to desired word count
+fn encrypt(mnemonic: &str, passphrase: &str, progress: Progress) -> Result<String> {
+    let original: MnemonicEx = mnemonic.parse()?;
+    if original.desired_size() < original.size() {
+        return Err(Bip38Error::InvalidSize);
+    }
+
+    // Generate a random salt if the desired size is greater than the original.
+    // The salt will be used to extend the mnemonic length.
+    let salt = &mut vec![0u8; (original.desired_size() - original.size()) / 3 * 4];
+    if !salt.is_empty() {
+        rand::thread_rng().fill_bytes(salt);
+    }
+
+    let mnemonic = original.encrypt_extend(passphrase, salt, progress)?;
+    Ok(mnemonic.to_string())
+}
+
+/// Decrypt mnemonic, shrunk to original word count
+fn decrypt(mnemonic: &str, passphrase: &str, progress: Progress) -> Result<String> {
+    let mnemonic: MnemonicEx = mnemonic.parse()?;
+    if mnemonic.desired_size() > mnemonic.size() {
+        return Err(Bip38Error::InvalidSize);
+    }
+    let original = mnemonic.decrypt_extend(passphrase, progress)?;
+    Ok(original.to_string())
+}
+
 trait ByteOperation {
     fn sha256_n(&self, n: usize) -> [u8; 32];
     fn xor(&mut self, other: &Self);
@@ -341,4 +399,24 @@ mod tests {
         println!("Encrypted: {encrypted}");
         Ok(())
     }
+
+    #[test]
+    fn test_mnemonic_progress() -> Result {
+        let data = "派 贤 博 如 恐 臂 诺 职 畜 给 压 钱 牲 案 隔";
+        let mut last = (0, 0);
+        let encrypted = data.mnemonic_encrypt_with_progress("123456", &mut |done, total| {
+            assert!(done >= last.0 && done <= total);
+            last = (done, total);
+            true
+        })?;
+        assert_eq!(last.0, last.1);
+        assert_eq!(
+            encrypted,
+            "坏 火 发 恐 晒 为 陕 伪 镜 锻 略 越 力 秦 音; 胞"
+        );
+
+        let r = encrypted.mnemonic_decrypt_with_progress("123456", &mut |_, _| false);
+        assert!(matches!(r, Err(Bip38Error::Cancelled)));
+        Ok(())
+    }
 }
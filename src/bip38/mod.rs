@@ -27,6 +27,8 @@ pub enum Bip38Error {
     InvalidSize,
     #[error("Inner error: {0}")]
     InnerError(String),
+    #[error("Cancelled")]
+    Cancelled,
 }
 
 macro_rules! derive_error {
@@ -49,3 +51,12 @@ derive_error!(
 );
 derive_error!(Bip38Error::InnerError, argon2::Error);
 derive_error!(Bip38Error::InnerError, bitcoin::bip32::Error);
+
+impl From<crate::progress::StretchError> for Bip38Error {
+    fn from(e: crate::progress::StretchError) -> Self {
+        match e {
+            crate::progress::StretchError::Cancelled => Bip38Error::Cancelled,
+            crate::progress::StretchError::OutputLen(e) => Bip38Error::InnerError(e.to_string()),
+        }
+    }
+}
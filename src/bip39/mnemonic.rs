@@ -1,4 +1,6 @@
 use super::{Bip39Error, Language};
+use crate::secret::{ImpSecret, protect};
+use bitcoin::Network;
 use bitcoin::bip32::Xpriv;
 use sha2::{Digest, Sha256};
 use xbits::{FromBits, XBits};
@@ -10,11 +12,13 @@ type Result<T> = std::result::Result<T, Bip39Error>;
 /// # Reference
 /// [1] - [BIP39 spec](https://bips.dev/39/)
 /// [2] - [Ref website](https://iancoleman.io/bip39/)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
 pub struct Mnemonic {
     words: Vec<String>,
     language: Language,
 }
+ImpSecret!(Mnemonic, words);
 
 #[allow(unused)]
 impl Mnemonic {
@@ -41,8 +45,7 @@ impl Mnemonic {
         let checksum = Sha256::digest(entropy)[0] & check_mask;
 
         // convert entropy to indices
-        let indices: Vec<usize> = [entropy.to_vec(), vec![checksum]]
-            .concat()
+        let indices: Vec<usize> = protect([entropy, &[checksum]].concat())
             .bits()
             .chunks(11)
             .take(size)
@@ -58,19 +61,25 @@ impl Mnemonic {
     }
 
     /// Generate a master key from the mnemonic phrase.
+    #[inline]
     pub fn to_master(&self, salt: &str) -> Result<Xpriv> {
-        let mnemonic = self.to_string();
+        self.to_master_in(salt, crate::DEFAULT_NETWORK)
+    }
+
+    /// Generate a master key of network from the mnemonic phrase.
+    pub fn to_master_in(&self, salt: &str, network: Network) -> Result<Xpriv> {
+        let mnemonic = protect(self.to_string());
         let salt = format!("mnemonic{salt}");
 
-        let mut seed: [u8; 64] = [0; 64];
+        let mut seed = protect([0u8; 64]);
         pbkdf2::pbkdf2_hmac::<sha2::Sha512>(
             mnemonic.as_bytes(),
             salt.as_bytes(),
             u32::pow(2, 11),
-            &mut seed,
+            &mut seed[..],
         );
 
-        Ok(Xpriv::new_master(crate::NETWORK, &seed)?)
+        Ok(Xpriv::new_master(network, &seed[..])?)
     }
 
     /// Mnemonic language
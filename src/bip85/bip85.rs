@@ -1,5 +1,6 @@
 use super::Password;
 use crate::bip39::{Language, Mnemonic};
+use crate::secret::{ImpSecret, protect};
 use bitcoin::{
     Address, CompressedPublicKey,
     bip32::{ChainCode, ChildNumber, Xpriv},
@@ -57,10 +58,12 @@ pub trait Bip85 {
 
     /// HD-Seed WIF  
     // Path format is m/83696968'/2'/{index}'
+    /// network of wif and address is same as the root key
     fn bip85_wallet(&self, index: u32) -> Bip85Result<Wif>;
 
     /// XPRV  
     // Path format is m/83696968'/32'/{index}'
+    /// network of xprv is same as the root key
     fn bip85_master(&self, index: u32) -> Bip85Result;
 
     /// PWD BASE64  
@@ -88,17 +91,17 @@ impl Bip85 for Xpriv {
         }
 
         let path = format!("m/83696968'/39'/{}'/{count}'/{index}'", language as u32);
-        let entropy = bip85_derive(self, &path)?[..(count as usize * 4 / 3)].to_vec();
-        let mnemonic = Mnemonic::new(&entropy, language)?;
+        let entropy = protect(bip85_derive(self, &path)?);
+        let mnemonic = Mnemonic::new(&entropy[..(count as usize * 4 / 3)], language)?;
         Ok(mnemonic.to_string())
     }
 
     fn bip85_wallet(&self, index: u32) -> Bip85Result<Wif> {
         let path = format!("m/83696968'/2'/{index}'");
-        let entropy = bip85_derive(self, &path)?;
-        let priv_key = bitcoin::PrivateKey::from_slice(&entropy[..32], crate::NETWORK)?;
+        let entropy = protect(bip85_derive(self, &path)?);
+        let priv_key = bitcoin::PrivateKey::from_slice(&entropy[..32], self.network)?;
         let pub_key = CompressedPublicKey::from_private_key(&Secp256k1::default(), &priv_key)?;
-        let addr = Address::p2shwpkh(&pub_key, crate::NETWORK);
+        let addr = Address::p2shwpkh(&pub_key, self.network);
         Ok(Wif {
             pk: priv_key.to_wif(),
             addr: addr.to_string(),
@@ -107,10 +110,10 @@ impl Bip85 for Xpriv {
 
     fn bip85_master(&self, index: u32) -> Bip85Result {
         let path = format!("m/83696968'/32'/{index}'");
-        let entropy = bip85_derive(self, &path)?;
+        let entropy = protect(bip85_derive(self, &path)?);
         let chain_code = ChainCode::from_hex(&entropy[..32].to_lower_hex_string())?;
         let xpriv = Xpriv {
-            network: crate::NETWORK,
+            network: self.network,
             depth: 0,
             parent_fingerprint: Default::default(),
             child_number: ChildNumber::Normal { index: 0 },
@@ -125,7 +128,7 @@ impl Bip85 for Xpriv {
             return Err(Bip85Error::InvalidParameter("20 <= pwd_len <= 86"));
         }
         let path = format!("m/83696968'/707764'/{pwd_len}'/{index}'");
-        let entropy = bip85_derive(self, &path)?;
+        let entropy = protect(bip85_derive(self, &path)?);
 
         Ok(entropy
             .bits()
@@ -161,7 +164,7 @@ mod bip85_test {
         const MASTER_KEY: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
         const DERIVED_WIF: &str = "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp";
         let master = bitcoin::bip32::Xpriv::from_str(MASTER_KEY)?;
-        let priv_key: String = master.bip85_wallet(0)?.pk;
+        let priv_key: String = master.bip85_wallet(0)?.pk.clone();
         assert_eq!(priv_key, DERIVED_WIF);
         Ok(())
     }
@@ -236,10 +239,12 @@ mod bip85_test {
 // use super::macros::{ImpDeref, ImpDisplay, ImpFrom, ImpPartialEq};
 
 /// String wrapper for extra functions
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
 pub struct Wif {
     /// Private key
     pub pk: String,
     /// Address
     pub addr: String,
 }
+ImpSecret!(Wif, pk);
@@ -0,0 +1,121 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Address chain
+ *      receive or change chain of an account, the chain node is derived once: `xpub/change`,
+ *      addresses are derived from the cached node without hardened derivation.
+ *
+ * [2] - Gap limit
+ *      scan addresses from index 0, stop after `gap_limit` unused addresses in a row.
+ *
+ * # Reference
+ * [1] - [BIP44 address gap limit](https://bips.dev/44/)
+**/
+use crate::derive::DeriveError;
+use crate::slip132::ScriptType;
+use bitcoin::{
+    Address, Network,
+    bip32::{ChildNumber, Xpub},
+    secp256k1::{Secp256k1, VerifyOnly},
+};
+use std::ops::Range;
+
+/// Receive or change address chain of an account
+#[derive(Debug, Clone)]
+pub struct AddressChain {
+    node: Xpub,
+    script: ScriptType,
+    network: Network,
+    secp: Secp256k1<VerifyOnly>,
+}
+
+impl AddressChain {
+    /// Address chain of account extended public key
+    /// # Parameters
+    /// - `account`: Account extended public key
+    /// - `change`: Receive chain `xpub/0`, or change chain `xpub/1`
+    /// - `script`: Single key script type, multisig types are invalid
+    /// - `network`: Network of addresses
+    pub fn new(
+        account: &Xpub,
+        change: bool,
+        script: ScriptType,
+        network: Network,
+    ) -> Result<Self, DeriveError> {
+        if matches!(script, ScriptType::P2shP2wsh | ScriptType::P2wsh) {
+            return Err(DeriveError::InvalidAccount(format!(
+                "{script:?} of single key chain"
+            )));
+        }
+        let secp = Secp256k1::verification_only();
+        let node = account.ckd_pub(&secp, ChildNumber::from_normal_idx(change as u32)?)?;
+        Ok(AddressChain {
+            node,
+            script,
+            network,
+            secp,
+        })
+    }
+
+    /// Derive address of index
+    pub fn address(&self, index: u32) -> Result<Address, DeriveError> {
+        let xpub = self
+            .node
+            .ckd_pub(&self.secp, ChildNumber::from_normal_idx(index)?)?;
+        Ok(script_address(&self.secp, &xpub, self.script, self.network))
+    }
+
+    /// Addresses of index range, derived lazily
+    /// # Returns
+    ///   iterator of (index, address)
+    pub fn range(
+        &self,
+        range: Range<u32>,
+    ) -> impl Iterator<Item = Result<(u32, Address), DeriveError>> + '_ {
+        range.map(|index| Ok((index, self.address(index)?)))
+    }
+
+    /// Scan used addresses from index 0 with gap limit
+    /// # Parameters
+    /// - `gap_limit`: Stop after number of unused addresses in a row, 20 of BIP44
+    /// - `is_used`: Whether an address has transactions
+    /// # Returns
+    ///   used addresses: (index, address)
+    pub fn scan(
+        &self,
+        gap_limit: u32,
+        mut is_used: impl FnMut(&Address) -> bool,
+    ) -> Result<Vec<(u32, Address)>, DeriveError> {
+        let (mut used, mut gap) = (vec![], 0);
+        for item in self.range(0..1 << 31) {
+            if gap >= gap_limit {
+                break;
+            }
+            let (index, address) = item?;
+            if is_used(&address) {
+                used.push((index, address));
+                gap = 0;
+            } else {
+                gap += 1;
+            }
+        }
+        Ok(used)
+    }
+}
+
+/// Address of single key script type
+fn script_address(
+    secp: &Secp256k1<VerifyOnly>,
+    xpub: &Xpub,
+    script: ScriptType,
+    network: Network,
+) -> Address {
+    let key = xpub.to_pub();
+    match script {
+        ScriptType::P2pkh => Address::p2pkh(key, network),
+        ScriptType::P2shP2wpkh => Address::p2shwpkh(&key, network),
+        ScriptType::P2wpkh => Address::p2wpkh(&key, network),
+        ScriptType::P2tr => Address::p2tr(secp, xpub.to_x_only_pub(), None, network),
+        ScriptType::P2shP2wsh | ScriptType::P2wsh => unreachable!("[artimonist] single key chain"),
+    }
+}
@@ -1,6 +1,11 @@
+use crate::chain::AddressChain;
+use crate::descriptor::{sortedmulti, with_checksum};
+use crate::slip132::{ScriptType, Slip132};
+#[cfg(feature = "extfmt")]
+use crate::slip132::{Slip132Priv, Slip132Pub};
 use bitcoin::{
-    Address, PublicKey, ScriptBuf,
-    bip32::{DerivationPath, Xpriv, Xpub},
+    Address, Network, NetworkKind, PrivateKey, PublicKey, ScriptBuf,
+    bip32::{DerivationPath, KeySource, Xpriv, Xpub},
     key::Secp256k1,
     script::Builder,
 };
@@ -8,20 +13,71 @@ use std::str::FromStr;
 
 type DeriveResult = Result<(String, String), DeriveError>;
 
+/// Derived account keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedAccount {
+    /// Account extended public key
+    pub xpub: Xpub,
+    /// Account extended private key
+    pub xpriv: Xpriv,
+    /// Key origin: fingerprint of master and derivation path
+    pub origin: KeySource,
+}
+
+/// Derived single key wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedWallet {
+    /// Wallet address
+    pub address: Address,
+    /// Wallet private key
+    pub private_key: PrivateKey,
+    /// Key origin: fingerprint of master and derivation path
+    pub origin: KeySource,
+}
+
+/// Derived multisig wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedMultisig {
+    /// Wallet address
+    pub address: Address,
+    /// Sorted multisig script, redeem script of p2sh or witness script of p2wsh
+    pub script: ScriptBuf,
+    /// Key origins of all keys, in order of accounts
+    pub origins: Vec<KeySource>,
+}
+
 pub trait DeriveInner {
-    fn derive(&self, path: &str) -> Result<(Xpub, Xpriv), DeriveError>;
-    fn multisig<const M: u8>(&self, paths: &[String]) -> Result<ScriptBuf, DeriveError>;
+    fn network(&self) -> Network;
+    fn derive(&self, path: &str) -> Result<DerivedAccount, DeriveError>;
+    fn multisig<const M: u8>(
+        &self,
+        paths: &[String],
+    ) -> Result<(ScriptBuf, Vec<KeySource>), DeriveError>;
 }
 
 impl DeriveInner for Xpriv {
+    /// Network inferred from extended key, test keys are testnet, coin type of paths follows it
+    #[inline]
+    fn network(&self) -> Network {
+        match self.network {
+            NetworkKind::Main => Network::Bitcoin,
+            NetworkKind::Test => Network::Testnet,
+        }
+    }
+
     /// Derive a key pair from derivation path
     #[inline]
-    fn derive(&self, path_str: &str) -> Result<(Xpub, Xpriv), DeriveError> {
+    fn derive(&self, path_str: &str) -> Result<DerivedAccount, DeriveError> {
         let secp = Secp256k1::default();
         let path = DerivationPath::from_str(path_str)?;
         let xpriv = self.derive_priv(&secp, &path)?;
         let xpub = Xpub::from_priv(&secp, &xpriv);
-        Ok((xpub, xpriv))
+        let origin = (self.fingerprint(&secp), path);
+        Ok(DerivedAccount {
+            xpub,
+            xpriv,
+            origin,
+        })
     }
 
     /// Create a multisig script
@@ -30,40 +86,67 @@ impl DeriveInner for Xpriv {
     ///  - `paths.len()`: Number of total signatures
     ///  - `paths`: Derivation paths
     /// # Returns
-    ///  - multisig script
-    fn multisig<const M: u8>(&self, paths: &[String]) -> Result<ScriptBuf, DeriveError> {
+    ///  - multisig script, key origins
+    fn multisig<const M: u8>(
+        &self,
+        paths: &[String],
+    ) -> Result<(ScriptBuf, Vec<KeySource>), DeriveError> {
         assert!(
             M <= paths.len() as u8 && paths.len() <= 15,
             "[artimonist] Overflow: M <= paths.len() <= 15"
         );
         // collect public keys
-        let secp = Secp256k1::default();
-        let mut pub_keys = paths
+        let accounts = paths
             .iter()
-            .map(|path| {
-                let path = DerivationPath::from_str(path)?;
-                let priv_key = self.derive_priv(&secp, &path)?.to_priv();
-                Ok(PublicKey::from_private_key(&secp, &priv_key))
-            })
+            .map(|path| self.derive(path))
             .collect::<Result<Vec<_>, DeriveError>>()?;
-        pub_keys.sort();
-        // create multisig script
-        let script = pub_keys
+        let pub_keys = accounts
             .iter()
-            .fold(Builder::new().push_int(M as i64), |builder, key| {
-                builder.push_key(key)
-            })
-            .push_int(pub_keys.len() as i64)
-            .push_opcode(bitcoin::opcodes::all::OP_CHECKMULTISIG)
-            .into_script();
-        Ok(script)
+            .map(|account| PublicKey::new(account.xpub.public_key))
+            .collect::<Vec<_>>();
+        let script = multisig_script(M, pub_keys);
+        let origins = accounts.into_iter().map(|account| account.origin).collect();
+        Ok((script, origins))
     }
 }
 
-#[cfg(not(feature = "testnet"))]
-const COIN: u8 = 0;
-#[cfg(feature = "testnet")]
-const COIN: u8 = 1;
+/// Sorted multisig script of public keys
+pub(crate) fn multisig_script(m: u8, mut pub_keys: Vec<PublicKey>) -> ScriptBuf {
+    pub_keys.sort();
+    pub_keys
+        .iter()
+        .fold(Builder::new().push_int(m as i64), |builder, key| {
+            builder.push_key(key)
+        })
+        .push_int(pub_keys.len() as i64)
+        .push_opcode(bitcoin::opcodes::all::OP_CHECKMULTISIG)
+        .into_script()
+}
+
+/// Coin type of derivation path, 0 for mainnet, 1 for all test networks
+#[inline]
+pub(crate) fn coin(network: Network) -> u8 {
+    match network {
+        Network::Bitcoin => 0,
+        _ => 1,
+    }
+}
+
+/// Account keys of network, versions of extended keys follow network
+#[inline]
+fn network_account(mut account: DerivedAccount, network: Network) -> DerivedAccount {
+    account.xpub.network = network.into();
+    account.xpriv.network = network.into();
+    account
+}
+
+/// Private key of network
+#[inline]
+fn private_key(xpriv: &Xpriv, network: Network) -> PrivateKey {
+    let mut priv_key = xpriv.to_priv();
+    priv_key.network = network.into();
+    priv_key
+}
 
 /// BIP32 derivation
 pub trait Bip32
@@ -76,8 +159,13 @@ where
     /// # Returns
     ///   (xpub, xpriv)
     fn bip32_account(&self, path: &str) -> DeriveResult {
+        self.bip32_derive_account(path)
+            .map(|account| (account.xpub.to_string(), account.xpriv.to_string()))
+    }
+
+    /// Derive a BIP32 account with key origin
+    fn bip32_derive_account(&self, path: &str) -> Result<DerivedAccount, DeriveError> {
         self.derive(path)
-            .map(|(xpub, xpriv)| (xpub.to_string(), xpriv.to_string()))
     }
 
     /// Derive a BIP32 wallet with custom path
@@ -91,9 +179,18 @@ where
     /// # Returns
     ///  (address, private_key): (p2pkh, wif)
     fn bip32_wallet(&self, path: &str) -> DeriveResult {
-        self.derive(path).map(|(xpub, xpriv)| {
-            let address = Address::p2pkh(xpub.to_pub(), crate::NETWORK);
-            (address.to_string(), xpriv.to_priv().to_wif())
+        self.bip32_derive_wallet(path)
+            .map(|wallet| (wallet.address.to_string(), wallet.private_key.to_wif()))
+    }
+
+    /// Derive a BIP32 p2pkh wallet with key origin
+    fn bip32_derive_wallet(&self, path: &str) -> Result<DerivedWallet, DeriveError> {
+        let network = self.network();
+        let account = self.derive(path)?;
+        Ok(DerivedWallet {
+            address: Address::p2pkh(account.xpub.to_pub(), network),
+            private_key: account.xpriv.to_priv(),
+            origin: account.origin,
         })
     }
 }
@@ -109,35 +206,119 @@ where
     /// # Returns
     ///   (xpub, xpriv)
     fn bip44_account(&self, account: u32) -> DeriveResult {
-        self.derive(&format!("m/44'/{COIN}'/{account}'"))
-            .map(|(xpub, xpriv)| (xpub.to_string(), xpriv.to_string()))
+        self.bip44_account_in(account, self.network())
+    }
+
+    /// [`bip44_account`](Self::bip44_account) of network
+    fn bip44_account_in(&self, account: u32, network: Network) -> DeriveResult {
+        self.bip44_derive_account_in(account, network)
+            .map(|account| (account.xpub.to_string(), account.xpriv.to_string()))
     }
 
-    /// Derive a wallet from a BIP44 account
+    /// Derive a BIP44 account with key origin
+    fn bip44_derive_account(&self, account: u32) -> Result<DerivedAccount, DeriveError> {
+        self.bip44_derive_account_in(account, self.network())
+    }
+
+    /// [`bip44_derive_account`](Self::bip44_derive_account) of network
+    fn bip44_derive_account_in(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<DerivedAccount, DeriveError> {
+        let coin = coin(network);
+        let account = self.derive(&format!("m/44'/{coin}'/{account}'"))?;
+        Ok(network_account(account, network))
+    }
+
+    /// Output descriptor of BIP44 account, p2pkh of receive and change addresses
+    /// # Returns
+    ///   `pkh([fingerprint/44'/coin'/account']xpub/<0;1>/*)#checksum`
+    fn bip44_descriptor(&self, account: u32) -> Result<String, DeriveError> {
+        self.bip44_descriptor_in(account, self.network())
+    }
+
+    /// [`bip44_descriptor`](Self::bip44_descriptor) of network
+    fn bip44_descriptor_in(&self, account: u32, network: Network) -> Result<String, DeriveError> {
+        let key = self
+            .bip44_derive_account_in(account, network)?
+            .descriptor_key();
+        Ok(with_checksum(format!("pkh({key})")))
+    }
+
+    /// Derive a wallet from BIP44 account
     /// # Derivation path
     ///   m/44'/0'/account'/0/index
     /// # Returns
     ///   (address, private_key): (p2pkh, wif)
     fn bip44_wallet(&self, account: u32, index: u32, change: bool) -> DeriveResult {
-        let change = if change { 1 } else { 0 };
-        self.derive(&format!("m/44'/{COIN}'/{account}'/{change}/{index}"))
-            .map(|(xpub, xpriv)| {
-                let address = Address::p2pkh(xpub.to_pub(), crate::NETWORK);
-                (address.to_string(), xpriv.to_priv().to_wif())
-            })
+        self.bip44_wallet_in(account, index, change, self.network())
     }
 
-    /// Derive a wallet from a BIP44 account
+    /// Derive a wallet from BIP44 account of network
+    /// # Derivation path
+    ///   m/44'/coin'/account'/0/index, coin is 0 for mainnet, 1 for test networks
+    /// # Returns
+    ///   (address, private_key): (p2pkh, wif)
+    fn bip44_wallet_in(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip44_derive_wallet(account, index, change, network)
+            .map(|wallet| (wallet.address.to_string(), wallet.private_key.to_wif()))
+    }
+
+    /// Derive a wallet from BIP44 account of network, with key origin
+    fn bip44_derive_wallet(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<DerivedWallet, DeriveError> {
+        let (coin, change) = (coin(network), if change { 1 } else { 0 });
+        let account = self.derive(&format!("m/44'/{coin}'/{account}'/{change}/{index}"))?;
+        Ok(DerivedWallet {
+            address: Address::p2pkh(account.xpub.to_pub(), network),
+            private_key: private_key(&account.xpriv, network),
+            origin: account.origin,
+        })
+    }
+
+    /// Receive or change address chain of BIP44 account
+    /// # Derivation path
+    ///   m/44'/0'/account'/change, derived once for all addresses of chain
+    fn bip44_chain(&self, account: u32, change: bool) -> Result<AddressChain, DeriveError> {
+        self.bip44_chain_in(account, change, self.network())
+    }
+
+    /// [`bip44_chain`](Self::bip44_chain) of network
+    fn bip44_chain_in(
+        &self,
+        account: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<AddressChain, DeriveError> {
+        let account = self.bip44_derive_account_in(account, network)?;
+        AddressChain::new(&account.xpub, change, ScriptType::P2pkh, network)
+    }
+
+    /// Derive a wallet from BIP44 account
     /// # Derivation path
     ///   m/44'/0'/account'/0/index'
     /// # Returns
     ///   (address, private_key): (p2pkh, wif)
     #[deprecated]
     fn bip44_harden(&self, account: u32, index: u32) -> DeriveResult {
-        self.derive(&format!("m/44'/{COIN}'/{account}'/0/{index}'"))
-            .map(|(xpub, xpriv)| {
-                let address = Address::p2pkh(xpub.to_pub(), crate::NETWORK);
-                (address.to_string(), xpriv.to_priv().to_wif())
+        let network = self.network();
+        let coin = coin(network);
+        self.derive(&format!("m/44'/{coin}'/{account}'/0/{index}'"))
+            .map(|account| {
+                let address = Address::p2pkh(account.xpub.to_pub(), network);
+                (address.to_string(), account.xpriv.to_priv().to_wif())
             })
     }
 
@@ -155,15 +336,84 @@ where
     /// # Returns
     ///   (address, redeem_script)
     fn bip44_multisig<const M: u8, const N: u8>(&self, account: u32, index: u32) -> DeriveResult {
+        self.bip44_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip44_multisig`](Self::bip44_multisig) of network
+    fn bip44_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip44_derive_multisig_in::<M, N>(account, index, network)
+            .map(|multisig| {
+                (
+                    multisig.address.to_string(),
+                    multisig.script.to_hex_string(),
+                )
+            })
+    }
+
+    /// Derive a multisig wallet from BIP44 accounts, with key origins
+    fn bip44_derive_multisig<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+    ) -> Result<DerivedMultisig, DeriveError> {
+        self.bip44_derive_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip44_derive_multisig`](Self::bip44_derive_multisig) of network
+    fn bip44_derive_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> Result<DerivedMultisig, DeriveError> {
         assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let coin = coin(network);
         let paths = (account..account + N as u32)
-            .map(|account: u32| format!("m/44'/{COIN}'/{account}'/0/{index}"))
+            .map(|account: u32| format!("m/44'/{coin}'/{account}'/0/{index}"))
             .collect::<Vec<_>>();
-        let script = self.multisig::<M>(paths.as_slice())?;
-        Ok((
-            Address::p2sh(&script, crate::NETWORK)?.to_string(),
-            script.to_hex_string(),
-        ))
+        let (script, origins) = self.multisig::<M>(paths.as_slice())?;
+        Ok(DerivedMultisig {
+            address: Address::p2sh(&script, network)?,
+            script,
+            origins,
+        })
+    }
+
+    /// Output descriptor of multisig wallets from BIP44 accounts
+    /// # Derivation path
+    ///   m/44'/0'/account'
+    ///   m/44'/0'/(account + 1)'
+    ///   ...
+    /// # Returns
+    ///   `sh(sortedmulti(M,[fingerprint/path]xpub/<0;1>/*,...))#checksum`
+    ///
+    /// public keys of multisig script are sorted, so it's `sortedmulti`.
+    fn bip44_multisig_descriptor<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+    ) -> Result<String, DeriveError> {
+        self.bip44_multisig_descriptor_in::<M, N>(account, self.network())
+    }
+
+    /// [`bip44_multisig_descriptor`](Self::bip44_multisig_descriptor) of network
+    fn bip44_multisig_descriptor_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<String, DeriveError> {
+        assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let accounts = (account..account + N as u32)
+            .map(|account| self.bip44_derive_account_in(account, network))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(with_checksum(format!(
+            "sh({})",
+            sortedmulti(M, accounts.iter().map(DerivedAccount::descriptor_key))
+        )))
     }
 }
 
@@ -195,8 +445,44 @@ where
     /// # Returns
     ///   (xpub, xpriv)
     fn bip49_account(&self, account: u32) -> DeriveResult {
-        self.derive(&format!("m/49'/{COIN}'/{account}'"))
-            .map(|(xpub, xpriv)| (Ypub(xpub).to_string(), Ypriv(xpriv).to_string()))
+        self.bip49_account_in(account, self.network())
+    }
+
+    /// [`bip49_account`](Self::bip49_account) of network
+    fn bip49_account_in(&self, account: u32, network: Network) -> DeriveResult {
+        self.bip49_derive_account_in(account, network)
+            .map(|account| account_keys(account, Slip132::Y))
+    }
+
+    /// Derive a BIP49 account with key origin
+    fn bip49_derive_account(&self, account: u32) -> Result<DerivedAccount, DeriveError> {
+        self.bip49_derive_account_in(account, self.network())
+    }
+
+    /// [`bip49_derive_account`](Self::bip49_derive_account) of network
+    fn bip49_derive_account_in(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<DerivedAccount, DeriveError> {
+        let coin = coin(network);
+        let account = self.derive(&format!("m/49'/{coin}'/{account}'"))?;
+        Ok(network_account(account, network))
+    }
+
+    /// Output descriptor of BIP49 account, p2sh-p2wpkh of receive and change addresses
+    /// # Returns
+    ///   `sh(wpkh([fingerprint/49'/coin'/account']xpub/<0;1>/*))#checksum`
+    fn bip49_descriptor(&self, account: u32) -> Result<String, DeriveError> {
+        self.bip49_descriptor_in(account, self.network())
+    }
+
+    /// [`bip49_descriptor`](Self::bip49_descriptor) of network
+    fn bip49_descriptor_in(&self, account: u32, network: Network) -> Result<String, DeriveError> {
+        let key = self
+            .bip49_derive_account_in(account, network)?
+            .descriptor_key();
+        Ok(with_checksum(format!("sh(wpkh({key}))")))
     }
 
     /// Derive a wallet from BIP49 account
@@ -205,12 +491,58 @@ where
     /// # Returns
     ///   (address, private_key): (p2shwpkh, wif)
     fn bip49_wallet(&self, account: u32, index: u32, change: bool) -> DeriveResult {
-        let change = if change { 1 } else { 0 };
-        self.derive(&format!("m/49'/{COIN}'/{account}'/{change}/{index}"))
-            .map(|(xpub, xpriv)| {
-                let address = Address::p2shwpkh(&xpub.to_pub(), crate::NETWORK);
-                (address.to_string(), xpriv.to_priv().to_wif())
-            })
+        self.bip49_wallet_in(account, index, change, self.network())
+    }
+
+    /// Derive a wallet from BIP49 account of network
+    /// # Derivation path
+    ///   m/49'/coin'/account'/0/index, coin is 0 for mainnet, 1 for test networks
+    /// # Returns
+    ///   (address, private_key): (p2shwpkh, wif)
+    fn bip49_wallet_in(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip49_derive_wallet(account, index, change, network)
+            .map(|wallet| (wallet.address.to_string(), wallet.private_key.to_wif()))
+    }
+
+    /// Derive a wallet from BIP49 account of network, with key origin
+    fn bip49_derive_wallet(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<DerivedWallet, DeriveError> {
+        let (coin, change) = (coin(network), if change { 1 } else { 0 });
+        let account = self.derive(&format!("m/49'/{coin}'/{account}'/{change}/{index}"))?;
+        Ok(DerivedWallet {
+            address: Address::p2shwpkh(&account.xpub.to_pub(), network),
+            private_key: private_key(&account.xpriv, network),
+            origin: account.origin,
+        })
+    }
+
+    /// Receive or change address chain of BIP49 account
+    /// # Derivation path
+    ///   m/49'/0'/account'/change, derived once for all addresses of chain
+    fn bip49_chain(&self, account: u32, change: bool) -> Result<AddressChain, DeriveError> {
+        self.bip49_chain_in(account, change, self.network())
+    }
+
+    /// [`bip49_chain`](Self::bip49_chain) of network
+    fn bip49_chain_in(
+        &self,
+        account: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<AddressChain, DeriveError> {
+        let account = self.bip49_derive_account_in(account, network)?;
+        AddressChain::new(&account.xpub, change, ScriptType::P2shP2wpkh, network)
     }
 
     /// Derive a wallet from BIP49 account
@@ -220,10 +552,12 @@ where
     ///   (address, private_key): (p2shwpkh, wif)
     #[deprecated]
     fn bip49_harden(&self, account: u32, index: u32) -> DeriveResult {
-        self.derive(&format!("m/49'/{COIN}'/{account}'/0/{index}'"))
-            .map(|(xpub, xpriv)| {
-                let address = Address::p2shwpkh(&xpub.to_pub(), crate::NETWORK);
-                (address.to_string(), xpriv.to_priv().to_wif())
+        let network = self.network();
+        let coin = coin(network);
+        self.derive(&format!("m/49'/{coin}'/{account}'/0/{index}'"))
+            .map(|account| {
+                let address = Address::p2shwpkh(&account.xpub.to_pub(), network);
+                (address.to_string(), account.xpriv.to_priv().to_wif())
             })
     }
 
@@ -239,17 +573,179 @@ where
     ///   m/49'/0'/(account + 2)'/0/index
     ///   ...
     /// # Returns
-    ///   (address, redeem_script)
+    ///   (address, redeem_script): (legacy p2sh, sorted multisig script)
+    ///
+    /// For P2SH-P2WSH address, use [`bip49_wsh_multisig`](Self::bip49_wsh_multisig).
     fn bip49_multisig<const M: u8, const N: u8>(&self, account: u32, index: u32) -> DeriveResult {
+        self.bip49_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip49_multisig`](Self::bip49_multisig) of network
+    fn bip49_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip49_derive_multisig_in::<M, N>(account, index, network)
+            .map(|multisig| {
+                (
+                    multisig.address.to_string(),
+                    multisig.script.to_hex_string(),
+                )
+            })
+    }
+
+    /// Derive a multisig wallet from BIP49 accounts, with key origins
+    fn bip49_derive_multisig<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+    ) -> Result<DerivedMultisig, DeriveError> {
+        self.bip49_derive_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip49_derive_multisig`](Self::bip49_derive_multisig) of network
+    fn bip49_derive_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> Result<DerivedMultisig, DeriveError> {
+        assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let coin = coin(network);
+        let paths = (account..account + N as u32)
+            .map(|account: u32| format!("m/49'/{coin}'/{account}'/0/{index}"))
+            .collect::<Vec<_>>();
+        let (script, origins) = self.multisig::<M>(paths.as_slice())?;
+        Ok(DerivedMultisig {
+            address: Address::p2sh(&script, network)?,
+            script,
+            origins,
+        })
+    }
+
+    /// Output descriptor of multisig wallets from BIP49 accounts
+    /// # Derivation path
+    ///   m/49'/0'/account'
+    ///   m/49'/0'/(account + 1)'
+    ///   ...
+    /// # Returns
+    ///   `sh(sortedmulti(M,[fingerprint/path]xpub/<0;1>/*,...))#checksum`
+    ///
+    /// public keys of multisig script are sorted, so it's `sortedmulti`.
+    fn bip49_multisig_descriptor<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+    ) -> Result<String, DeriveError> {
+        self.bip49_multisig_descriptor_in::<M, N>(account, self.network())
+    }
+
+    /// [`bip49_multisig_descriptor`](Self::bip49_multisig_descriptor) of network
+    fn bip49_multisig_descriptor_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<String, DeriveError> {
+        assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let accounts = (account..account + N as u32)
+            .map(|account| self.bip49_derive_account_in(account, network))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(with_checksum(format!(
+            "sh({})",
+            sortedmulti(M, accounts.iter().map(DerivedAccount::descriptor_key))
+        )))
+    }
+
+    /// Derive a nested segwit multisig wallet from BIP49 accounts
+    /// # Parameters
+    /// - `M`: Number of required signatures
+    /// - `N`: Number of total signatures
+    /// - `account`: Account start index (total use N accounts)
+    /// - `index`: Wallet index
+    /// # Derivation path
+    ///   m/49'/0'/account'/0/index
+    ///   m/49'/0'/(account + 1)'/0/index
+    ///   ...
+    /// # Returns
+    ///   (address, witness_script): (p2sh-p2wsh, sorted multisig script)
+    fn bip49_wsh_multisig<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+    ) -> DeriveResult {
+        self.bip49_wsh_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip49_wsh_multisig`](Self::bip49_wsh_multisig) of network
+    fn bip49_wsh_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip49_derive_wsh_multisig_in::<M, N>(account, index, network)
+            .map(|multisig| {
+                (
+                    multisig.address.to_string(),
+                    multisig.script.to_hex_string(),
+                )
+            })
+    }
+
+    /// Derive a nested segwit multisig wallet from BIP49 accounts, with key origins
+    fn bip49_derive_wsh_multisig<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+    ) -> Result<DerivedMultisig, DeriveError> {
+        self.bip49_derive_wsh_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip49_derive_wsh_multisig`](Self::bip49_derive_wsh_multisig) of network
+    fn bip49_derive_wsh_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> Result<DerivedMultisig, DeriveError> {
         assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let coin = coin(network);
         let paths = (account..account + N as u32)
-            .map(|account: u32| format!("m/49'/{COIN}'/{account}'/0/{index}"))
+            .map(|account: u32| format!("m/49'/{coin}'/{account}'/0/{index}"))
             .collect::<Vec<_>>();
-        let script = self.multisig::<M>(paths.as_slice())?;
-        Ok((
-            Address::p2sh(&script, crate::NETWORK)?.to_string(),
-            script.to_hex_string(),
-        ))
+        let (script, origins) = self.multisig::<M>(paths.as_slice())?;
+        Ok(DerivedMultisig {
+            address: Address::p2shwsh(&script, network),
+            script,
+            origins,
+        })
+    }
+
+    /// Output descriptor of nested segwit multisig wallets from BIP49 accounts
+    /// # Returns
+    ///   `sh(wsh(sortedmulti(M,[fingerprint/path]xpub/<0;1>/*,...)))#checksum`
+    fn bip49_wsh_multisig_descriptor<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+    ) -> Result<String, DeriveError> {
+        self.bip49_wsh_multisig_descriptor_in::<M, N>(account, self.network())
+    }
+
+    /// [`bip49_wsh_multisig_descriptor`](Self::bip49_wsh_multisig_descriptor) of network
+    fn bip49_wsh_multisig_descriptor_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<String, DeriveError> {
+        assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let accounts = (account..account + N as u32)
+            .map(|account| self.bip49_derive_account_in(account, network))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(with_checksum(format!(
+            "sh(wsh({}))",
+            sortedmulti(M, accounts.iter().map(DerivedAccount::descriptor_key))
+        )))
     }
 }
 
@@ -264,8 +760,44 @@ where
     /// # Returns
     ///   (xpub, xpriv)
     fn bip84_account(&self, account: u32) -> DeriveResult {
-        self.derive(&format!("m/84'/{COIN}'/{account}'"))
-            .map(|(xpub, xpriv)| (Zpub(xpub).to_string(), Zpriv(xpriv).to_string()))
+        self.bip84_account_in(account, self.network())
+    }
+
+    /// [`bip84_account`](Self::bip84_account) of network
+    fn bip84_account_in(&self, account: u32, network: Network) -> DeriveResult {
+        self.bip84_derive_account_in(account, network)
+            .map(|account| account_keys(account, Slip132::Z))
+    }
+
+    /// Derive a BIP84 account with key origin
+    fn bip84_derive_account(&self, account: u32) -> Result<DerivedAccount, DeriveError> {
+        self.bip84_derive_account_in(account, self.network())
+    }
+
+    /// [`bip84_derive_account`](Self::bip84_derive_account) of network
+    fn bip84_derive_account_in(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<DerivedAccount, DeriveError> {
+        let coin = coin(network);
+        let account = self.derive(&format!("m/84'/{coin}'/{account}'"))?;
+        Ok(network_account(account, network))
+    }
+
+    /// Output descriptor of BIP84 account, p2wpkh of receive and change addresses
+    /// # Returns
+    ///   `wpkh([fingerprint/84'/coin'/account']xpub/<0;1>/*)#checksum`
+    fn bip84_descriptor(&self, account: u32) -> Result<String, DeriveError> {
+        self.bip84_descriptor_in(account, self.network())
+    }
+
+    /// [`bip84_descriptor`](Self::bip84_descriptor) of network
+    fn bip84_descriptor_in(&self, account: u32, network: Network) -> Result<String, DeriveError> {
+        let key = self
+            .bip84_derive_account_in(account, network)?
+            .descriptor_key();
+        Ok(with_checksum(format!("wpkh({key})")))
     }
 
     /// Derive a wallet from BIP84 account
@@ -274,16 +806,58 @@ where
     /// # Returns
     ///   (address, private_key): (p2wpkh, wif)
     fn bip84_wallet(&self, account: u32, index: u32, change: bool) -> DeriveResult {
-        let network = match crate::NETWORK {
-            bitcoin::NetworkKind::Main => bitcoin::Network::Bitcoin,
-            bitcoin::NetworkKind::Test => bitcoin::Network::Testnet,
-        };
-        let change = if change { 1 } else { 0 };
-        self.derive(&format!("m/84'/{COIN}'/{account}'/{change}/{index}"))
-            .map(|(xpub, xpriv)| {
-                let address = Address::p2wpkh(&xpub.to_pub(), network);
-                (address.to_string(), xpriv.to_priv().to_wif())
-            })
+        self.bip84_wallet_in(account, index, change, self.network())
+    }
+
+    /// Derive a wallet from BIP84 account of network
+    /// # Derivation path
+    ///   m/84'/coin'/account'/0/index, coin is 0 for mainnet, 1 for test networks
+    /// # Returns
+    ///   (address, private_key): (p2wpkh, wif)
+    fn bip84_wallet_in(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip84_derive_wallet(account, index, change, network)
+            .map(|wallet| (wallet.address.to_string(), wallet.private_key.to_wif()))
+    }
+
+    /// Derive a wallet from BIP84 account of network, with key origin
+    fn bip84_derive_wallet(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<DerivedWallet, DeriveError> {
+        let (coin, change) = (coin(network), if change { 1 } else { 0 });
+        let account = self.derive(&format!("m/84'/{coin}'/{account}'/{change}/{index}"))?;
+        Ok(DerivedWallet {
+            address: Address::p2wpkh(&account.xpub.to_pub(), network),
+            private_key: private_key(&account.xpriv, network),
+            origin: account.origin,
+        })
+    }
+
+    /// Receive or change address chain of BIP84 account
+    /// # Derivation path
+    ///   m/84'/0'/account'/change, derived once for all addresses of chain
+    fn bip84_chain(&self, account: u32, change: bool) -> Result<AddressChain, DeriveError> {
+        self.bip84_chain_in(account, change, self.network())
+    }
+
+    /// [`bip84_chain`](Self::bip84_chain) of network
+    fn bip84_chain_in(
+        &self,
+        account: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<AddressChain, DeriveError> {
+        let account = self.bip84_derive_account_in(account, network)?;
+        AddressChain::new(&account.xpub, change, ScriptType::P2wpkh, network)
     }
 
     /// Derive a wallet from BIP84 account
@@ -293,14 +867,12 @@ where
     ///   (address, private_key): (p2wpkh, wif)
     #[deprecated]
     fn bip84_harden(&self, account: u32, index: u32) -> DeriveResult {
-        let network = match crate::NETWORK {
-            bitcoin::NetworkKind::Main => bitcoin::Network::Bitcoin,
-            bitcoin::NetworkKind::Test => bitcoin::Network::Testnet,
-        };
-        self.derive(&format!("m/84'/{COIN}'/{account}'/0/{index}'"))
-            .map(|(xpub, xpriv)| {
-                let address = Address::p2wpkh(&xpub.to_pub(), network);
-                (address.to_string(), xpriv.to_priv().to_wif())
+        let network = self.network();
+        let coin = coin(network);
+        self.derive(&format!("m/84'/{coin}'/{account}'/0/{index}'"))
+            .map(|account| {
+                let address = Address::p2wpkh(&account.xpub.to_pub(), network);
+                (address.to_string(), account.xpriv.to_priv().to_wif())
             })
     }
 
@@ -316,118 +888,331 @@ where
     ///   m/84'/0'/(account + 2)'/0/index
     ///   ...
     /// # Returns
-    ///   (address, redeem_script)
+    ///   (address, redeem_script): (legacy p2sh, sorted multisig script)
+    ///
+    /// For P2WSH address, use [`bip84_wsh_multisig`](Self::bip84_wsh_multisig).
     fn bip84_multisig<const M: u8, const N: u8>(&self, account: u32, index: u32) -> DeriveResult {
+        self.bip84_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip84_multisig`](Self::bip84_multisig) of network
+    fn bip84_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip84_derive_multisig_in::<M, N>(account, index, network)
+            .map(|multisig| {
+                (
+                    multisig.address.to_string(),
+                    multisig.script.to_hex_string(),
+                )
+            })
+    }
+
+    /// Derive a multisig wallet from BIP84 accounts, with key origins
+    fn bip84_derive_multisig<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+    ) -> Result<DerivedMultisig, DeriveError> {
+        self.bip84_derive_multisig_in::<M, N>(account, index, self.network())
+    }
+
+    /// [`bip84_derive_multisig`](Self::bip84_derive_multisig) of network
+    fn bip84_derive_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> Result<DerivedMultisig, DeriveError> {
         assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let coin = coin(network);
         let paths = (account..account + N as u32)
-            .map(|account: u32| format!("m/84'/{COIN}'/{account}'/0/{index}"))
+            .map(|account: u32| format!("m/84'/{coin}'/{account}'/0/{index}"))
             .collect::<Vec<_>>();
-        let script = self.multisig::<M>(paths.as_slice())?;
-        Ok((
-            Address::p2sh(&script, crate::NETWORK)?.to_string(),
-            script.to_hex_string(),
-        ))
+        let (script, origins) = self.multisig::<M>(paths.as_slice())?;
+        Ok(DerivedMultisig {
+            address: Address::p2sh(&script, network)?,
+            script,
+            origins,
+        })
     }
-}
 
-impl Bip32 for Xpriv {}
-impl Bip44 for Xpriv {}
-impl Bip49 for Xpriv {}
-impl Bip84 for Xpriv {}
+    /// Output descriptor of multisig wallets from BIP84 accounts
+    /// # Derivation path
+    ///   m/84'/0'/account'
+    ///   m/84'/0'/(account + 1)'
+    ///   ...
+    /// # Returns
+    ///   `sh(sortedmulti(M,[fingerprint/path]xpub/<0;1>/*,...))#checksum`
+    ///
+    /// public keys of multisig script are sorted, so it's `sortedmulti`.
+    fn bip84_multisig_descriptor<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+    ) -> Result<String, DeriveError> {
+        self.bip84_multisig_descriptor_in::<M, N>(account, self.network())
+    }
+
+    /// [`bip84_multisig_descriptor`](Self::bip84_multisig_descriptor) of network
+    fn bip84_multisig_descriptor_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<String, DeriveError> {
+        assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let accounts = (account..account + N as u32)
+            .map(|account| self.bip84_derive_account_in(account, network))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(with_checksum(format!(
+            "sh({})",
+            sortedmulti(M, accounts.iter().map(DerivedAccount::descriptor_key))
+        )))
+    }
 
-struct Ypriv(pub Xpriv);
-struct Ypub(pub Xpub);
-struct Zpriv(pub Xpriv);
-struct Zpub(pub Xpub);
+    /// Derive a native segwit multisig wallet from BIP84 accounts
+    /// # Parameters
+    /// - `M`: Number of required signatures
+    /// - `N`: Number of total signatures
+    /// - `account`: Account start index (total use N accounts)
+    /// - `index`: Wallet index
+    /// # Derivation path
+    ///   m/84'/0'/account'/0/index
+    ///   m/84'/0'/(account + 1)'/0/index
+    ///   ...
+    /// # Returns
+    ///   (address, witness_script): (p2wsh, sorted multisig script)
+    fn bip84_wsh_multisig<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+    ) -> DeriveResult {
+        self.bip84_wsh_multisig_in::<M, N>(account, index, self.network())
+    }
 
-impl std::fmt::Display for Ypriv {
-    #[cfg(not(feature = "extfmt"))]
-    #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+    /// [`bip84_wsh_multisig`](Self::bip84_wsh_multisig) of network
+    fn bip84_wsh_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip84_derive_wsh_multisig_in::<M, N>(account, index, network)
+            .map(|multisig| {
+                (
+                    multisig.address.to_string(),
+                    multisig.script.to_hex_string(),
+                )
+            })
     }
 
-    #[cfg(feature = "extfmt")]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use bitcoin::NetworkKind;
+    /// Derive a native segwit multisig wallet from BIP84 accounts, with key origins
+    fn bip84_derive_wsh_multisig<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+    ) -> Result<DerivedMultisig, DeriveError> {
+        self.bip84_derive_wsh_multisig_in::<M, N>(account, index, self.network())
+    }
 
-        const BIP49_VER_BYTES_MAINNET_PRIVATE: u32 = 0x049d7878; // ypriv
-        const BIP49_VER_BYTES_TESTNET_PRIVATE: u32 = 0x044a4e28; // upriv
+    /// [`bip84_derive_wsh_multisig`](Self::bip84_derive_wsh_multisig) of network
+    fn bip84_derive_wsh_multisig_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        index: u32,
+        network: Network,
+    ) -> Result<DerivedMultisig, DeriveError> {
+        assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let coin = coin(network);
+        let paths = (account..account + N as u32)
+            .map(|account: u32| format!("m/84'/{coin}'/{account}'/0/{index}"))
+            .collect::<Vec<_>>();
+        let (script, origins) = self.multisig::<M>(paths.as_slice())?;
+        Ok(DerivedMultisig {
+            address: Address::p2wsh(&script, network),
+            script,
+            origins,
+        })
+    }
 
-        match crate::NETWORK {
-            NetworkKind::Main => encode_fmt::<BIP49_VER_BYTES_MAINNET_PRIVATE>(f, &self.0.encode()),
-            NetworkKind::Test => encode_fmt::<BIP49_VER_BYTES_TESTNET_PRIVATE>(f, &self.0.encode()),
-        }
+    /// Output descriptor of native segwit multisig wallets from BIP84 accounts
+    /// # Returns
+    ///   `wsh(sortedmulti(M,[fingerprint/path]xpub/<0;1>/*,...))#checksum`
+    fn bip84_wsh_multisig_descriptor<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+    ) -> Result<String, DeriveError> {
+        self.bip84_wsh_multisig_descriptor_in::<M, N>(account, self.network())
     }
-}
 
-impl std::fmt::Display for Ypub {
-    #[cfg(not(feature = "extfmt"))]
-    #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+    /// [`bip84_wsh_multisig_descriptor`](Self::bip84_wsh_multisig_descriptor) of network
+    fn bip84_wsh_multisig_descriptor_in<const M: u8, const N: u8>(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<String, DeriveError> {
+        assert!(M <= N && N <= 15, "[artimonist] Overflow: M <= N <= 15");
+        let accounts = (account..account + N as u32)
+            .map(|account| self.bip84_derive_account_in(account, network))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(with_checksum(format!(
+            "wsh({})",
+            sortedmulti(M, accounts.iter().map(DerivedAccount::descriptor_key))
+        )))
     }
+}
 
-    #[cfg(feature = "extfmt")]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use bitcoin::NetworkKind;
+/// BIP86 derivation
+/// Key derivation for single key P2TR outputs, key path spending only.
+///
+/// # Examples
+/// ```
+/// use artimonist::{BIP39, BIP86};
+///
+/// let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+/// let master = mnemonic.mnemonic_to_master("")?;
+/// # #[cfg(not(feature = "testnet"))]
+/// assert_eq!(master.bip86_wallet(0, 0, false)?.0, "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr");
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+// # Reference
+// [1] - [BIP86 spec](https://bips.dev/86/)
+pub trait Bip86
+where
+    Self: DeriveInner,
+{
+    /// Derive a BIP86 account
+    /// # Derivation path
+    ///   m/86'/0'/account'
+    /// # Returns
+    ///   (xpub, xpriv)
+    fn bip86_account(&self, account: u32) -> DeriveResult {
+        self.bip86_account_in(account, self.network())
+    }
 
-        const BIP49_VER_BYTES_MAINNET_PUBLIC: u32 = 0x049d7cb2; // ypub
-        const BIP49_VER_BYTES_TESTNET_PUBLIC: u32 = 0x044a5262; // upub
+    /// [`bip86_account`](Self::bip86_account) of network
+    fn bip86_account_in(&self, account: u32, network: Network) -> DeriveResult {
+        self.bip86_derive_account_in(account, network)
+            .map(|account| (account.xpub.to_string(), account.xpriv.to_string()))
+    }
 
-        match crate::NETWORK {
-            NetworkKind::Main => encode_fmt::<BIP49_VER_BYTES_MAINNET_PUBLIC>(f, &self.0.encode()),
-            NetworkKind::Test => encode_fmt::<BIP49_VER_BYTES_TESTNET_PUBLIC>(f, &self.0.encode()),
-        }
+    /// Derive a BIP86 account with key origin
+    fn bip86_derive_account(&self, account: u32) -> Result<DerivedAccount, DeriveError> {
+        self.bip86_derive_account_in(account, self.network())
     }
-}
 
-impl std::fmt::Display for Zpriv {
-    #[cfg(not(feature = "extfmt"))]
-    #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+    /// [`bip86_derive_account`](Self::bip86_derive_account) of network
+    fn bip86_derive_account_in(
+        &self,
+        account: u32,
+        network: Network,
+    ) -> Result<DerivedAccount, DeriveError> {
+        let coin = coin(network);
+        let account = self.derive(&format!("m/86'/{coin}'/{account}'"))?;
+        Ok(network_account(account, network))
     }
 
-    #[cfg(feature = "extfmt")]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use bitcoin::NetworkKind;
+    /// Output descriptor of BIP86 account, p2tr of receive and change addresses
+    /// # Returns
+    ///   `tr([fingerprint/86'/coin'/account']xpub/<0;1>/*)#checksum`
+    fn bip86_descriptor(&self, account: u32) -> Result<String, DeriveError> {
+        self.bip86_descriptor_in(account, self.network())
+    }
 
-        const BIP84_VER_BYTES_MAINNET_PRIVATE: u32 = 0x04b2430c; // zpriv
-        const BIP84_VER_BYTES_TESTNET_PRIVATE: u32 = 0x045f18bc; // vpriv
+    /// [`bip86_descriptor`](Self::bip86_descriptor) of network
+    fn bip86_descriptor_in(&self, account: u32, network: Network) -> Result<String, DeriveError> {
+        let key = self
+            .bip86_derive_account_in(account, network)?
+            .descriptor_key();
+        Ok(with_checksum(format!("tr({key})")))
+    }
 
-        match crate::NETWORK {
-            NetworkKind::Main => encode_fmt::<BIP84_VER_BYTES_MAINNET_PRIVATE>(f, &self.0.encode()),
-            NetworkKind::Test => encode_fmt::<BIP84_VER_BYTES_TESTNET_PRIVATE>(f, &self.0.encode()),
-        }
+    /// Derive a wallet from BIP86 account
+    /// # Derivation path
+    ///   m/86'/0'/account'/0/index
+    /// # Returns
+    ///   (address, private_key): (p2tr, wif)
+    fn bip86_wallet(&self, account: u32, index: u32, change: bool) -> DeriveResult {
+        self.bip86_wallet_in(account, index, change, self.network())
     }
-}
 
-impl std::fmt::Display for Zpub {
-    #[cfg(not(feature = "extfmt"))]
-    #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+    /// Derive a wallet from BIP86 account of network
+    /// # Derivation path
+    ///   m/86'/coin'/account'/0/index, coin is 0 for mainnet, 1 for test networks
+    /// # Returns
+    ///   (address, private_key): (p2tr, wif)
+    fn bip86_wallet_in(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> DeriveResult {
+        self.bip86_derive_wallet(account, index, change, network)
+            .map(|wallet| (wallet.address.to_string(), wallet.private_key.to_wif()))
     }
 
-    #[cfg(feature = "extfmt")]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use bitcoin::NetworkKind;
+    /// Derive a wallet from BIP86 account of network, with key origin
+    ///
+    /// private key is the internal key, output key is tweaked without script tree.
+    fn bip86_derive_wallet(
+        &self,
+        account: u32,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<DerivedWallet, DeriveError> {
+        let (coin, change) = (coin(network), if change { 1 } else { 0 });
+        let account = self.derive(&format!("m/86'/{coin}'/{account}'/{change}/{index}"))?;
+        let secp = Secp256k1::verification_only();
+        Ok(DerivedWallet {
+            address: Address::p2tr(&secp, account.xpub.to_x_only_pub(), None, network),
+            private_key: private_key(&account.xpriv, network),
+            origin: account.origin,
+        })
+    }
 
-        const BIP84_VER_BYTES_MAINNET_PUBLIC: u32 = 0x04b24746; // zpub
-        const BIP84_VER_BYTES_TESTNET_PUBLIC: u32 = 0x045f1cf6; // vpub
+    /// Receive or change address chain of BIP86 account
+    /// # Derivation path
+    ///   m/86'/0'/account'/change, derived once for all addresses of chain
+    fn bip86_chain(&self, account: u32, change: bool) -> Result<AddressChain, DeriveError> {
+        self.bip86_chain_in(account, change, self.network())
+    }
 
-        match crate::NETWORK {
-            NetworkKind::Main => encode_fmt::<BIP84_VER_BYTES_MAINNET_PUBLIC>(f, &self.0.encode()),
-            NetworkKind::Test => encode_fmt::<BIP84_VER_BYTES_TESTNET_PUBLIC>(f, &self.0.encode()),
-        }
+    /// [`bip86_chain`](Self::bip86_chain) of network
+    fn bip86_chain_in(
+        &self,
+        account: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<AddressChain, DeriveError> {
+        let account = self.bip86_derive_account_in(account, network)?;
+        AddressChain::new(&account.xpub, change, ScriptType::P2tr, network)
     }
 }
 
+impl Bip32 for Xpriv {}
+impl Bip44 for Xpriv {}
+impl Bip49 for Xpriv {}
+impl Bip84 for Xpriv {}
+impl Bip86 for Xpriv {}
+
+/// Account keys of SLIP-132 prefix with `extfmt` feature, standard xpub and xprv without it
 #[cfg(feature = "extfmt")]
-fn encode_fmt<const PRE: u32>(f: &mut std::fmt::Formatter<'_>, data: &[u8]) -> std::fmt::Result {
-    let new_data = [&PRE.to_be_bytes(), &data[4..]].concat();
-    write!(f, "{}", bitcoin::base58::encode_check(&new_data))
+fn account_keys(account: DerivedAccount, prefix: Slip132) -> (String, String) {
+    (
+        Slip132Pub::new(account.xpub, prefix).to_string(),
+        Slip132Priv::new(account.xpriv, prefix).to_string(),
+    )
+}
+
+/// Account keys of SLIP-132 prefix with `extfmt` feature, standard xpub and xprv without it
+#[cfg(not(feature = "extfmt"))]
+#[inline(always)]
+fn account_keys(account: DerivedAccount, _prefix: Slip132) -> (String, String) {
+    (account.xpub.to_string(), account.xpriv.to_string())
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -438,4 +1223,13 @@ pub enum DeriveError {
     /// Bip32 Error
     #[error("bip32 error: {0}")]
     Bip32Error(#[from] bitcoin::bip32::Error),
+    /// Invalid multisig
+    #[error("invalid multisig: {0}")]
+    InvalidMultisig(&'static str),
+    /// Invalid cosigner
+    #[error("invalid cosigner: {0}")]
+    InvalidCosigner(String),
+    /// Invalid account extended public key
+    #[error("invalid account: {0}")]
+    InvalidAccount(String),
 }
@@ -0,0 +1,97 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Output descriptors
+ *      account keys are exported with key origin and extended public key,
+ *      receive and change branches in one multipath descriptor: `<0;1>`.
+ *
+ * [2] - Checksum
+ *      every descriptor ends with `#checksum` of BIP380.
+ *
+ * # Reference
+ * [1] - [BIP380](https://bips.dev/380/)
+ * [2] - [BIP389](https://bips.dev/389/)
+**/
+use crate::derive::DerivedAccount;
+
+const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+impl DerivedAccount {
+    /// Descriptor key of account, with key origin and multipath of receive and change
+    /// # Returns
+    ///   `[fingerprint/path]xpub/<0;1>/*`
+    pub fn descriptor_key(&self) -> String {
+        let (fingerprint, path) = &self.origin;
+        format!("[{fingerprint}/{path}]{}/<0;1>/*", self.xpub)
+    }
+}
+
+/// Append BIP380 checksum to descriptor
+pub(crate) fn with_checksum(desc: String) -> String {
+    let checksum = checksum(&desc).expect("[artimonist] descriptor charset");
+    format!("{desc}#{checksum}")
+}
+
+/// Sorted multisig descriptor of descriptor keys
+pub(crate) fn sortedmulti(m: u8, keys: impl Iterator<Item = String>) -> String {
+    let keys = keys.collect::<Vec<_>>().join(",");
+    format!("sortedmulti({m},{keys})")
+}
+
+/// BIP380 descriptor checksum, `None` if any character out of charset
+fn checksum(desc: &str) -> Option<String> {
+    let (mut c, mut cls, mut cls_count) = (1_u64, 0_u64, 0);
+    for ch in desc.chars() {
+        let pos = INPUT_CHARSET.find(ch)? as u64;
+        c = polymod(c, pos & 31);
+        cls = cls * 3 + (pos >> 5);
+        cls_count += 1;
+        if cls_count == 3 {
+            c = polymod(c, cls);
+            (cls, cls_count) = (0, 0);
+        }
+    }
+    if cls_count > 0 {
+        c = polymod(c, cls);
+    }
+    (0..8).for_each(|_| c = polymod(c, 0));
+    c ^= 1;
+    let checksum = (0..8)
+        .map(|j| CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+        .collect();
+    Some(checksum)
+}
+
+#[inline]
+fn polymod(c: u64, val: u64) -> u64 {
+    const GENERATOR: [u64; 5] = [
+        0xf5dee51989,
+        0xa9fdca3312,
+        0x1bab10e32d,
+        0x3706b1677a,
+        0x644d626ffd,
+    ];
+    let c0 = c >> 35;
+    let c = ((c & 0x7ffffffff) << 5) ^ val;
+    GENERATOR
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| c0 >> i & 1 == 1)
+        .fold(c, |c, (_, g)| c ^ g)
+}
+
+#[cfg(test)]
+mod descriptor_test {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+        assert_eq!(
+            with_checksum("pkh([d34db33f/44'/0'/0']xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1/*)".to_owned()),
+            "pkh([d34db33f/44'/0'/0']xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1/*)#ml40v0wf"
+        );
+        assert_eq!(checksum("raw(deadbeef)\u{00e9}"), None);
+    }
+}
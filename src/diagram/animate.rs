@@ -34,12 +34,15 @@
  *      0b0xxx_xxxx
  *      x bits indices char position in diagram.
 **/
-use super::{GenericDiagram, Result};
+use super::{Error, GenericDiagram, Result, unpack_cells, verify_checksum};
+use crate::secret::ImpSecret;
 use bitcoin::hashes::{Hash, sha256};
 
 /// Animate Diagram
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
 pub struct AnimateDiagram(pub Vec<[[Option<char>; 7]; 7]>);
+ImpSecret!(AnimateDiagram, 0);
 
 impl GenericDiagram for AnimateDiagram {
     type Item = char;
@@ -70,6 +73,47 @@ impl GenericDiagram for AnimateDiagram {
     }
 }
 
+impl AnimateDiagram {
+    /// Deserialize animate diagram from secret data of `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut data = verify_checksum(data)?;
+
+        // frame indices from right to left, until end frame
+        let mut frames = vec![];
+        loop {
+            let (rest, indices) = data
+                .split_last_chunk::<7>()
+                .ok_or(Error::InvalidData("data too short"))?;
+            if indices[1] & 0x80 == 0 || indices[2..].iter().any(|v| v & 0x80 != 0) {
+                return Err(Error::InvalidVersion);
+            }
+            let mut indices = *indices;
+            let end_frame = indices[0] & 0x80 != 0;
+            indices[0] &= 0x7f;
+            indices[1] &= 0x7f;
+            frames.push(indices);
+            data = rest;
+            if end_frame {
+                break;
+            }
+        }
+
+        // chars order from end frame to first frame
+        let str = std::str::from_utf8(data).map_err(|_| Error::InvalidData("invalid utf8"))?;
+        let mut chars = str.chars();
+        let mut cube = frames
+            .iter()
+            .rev()
+            .map(|indices| unpack_cells(indices, &mut chars))
+            .collect::<Result<Vec<_>>>()?;
+        if chars.next().is_some() {
+            return Err(Error::InvalidData("items count"));
+        }
+        cube.reverse();
+        Ok(Self(cube))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,6 +126,7 @@ mod tests {
         let diagram = AnimateDiagram(cube);
 
         let bytes = diagram.to_bytes()?;
+        assert_eq!(AnimateDiagram::from_bytes(&bytes)?, diagram);
         assert_eq!(
             &bytes[51..],
             [
@@ -100,4 +145,30 @@ mod tests {
         assert_eq!(master.to_string(), MASTER);
         Ok(())
     }
+
+    #[test]
+    fn test_animate_from_bytes() -> Result<()> {
+        let mut frame = [[None; 7]; 7];
+        frame[0][0] = Some('😊');
+        frame[6][6] = Some('A');
+        let diagram = AnimateDiagram(vec![frame]);
+        assert_eq!(AnimateDiagram::from_bytes(&diagram.to_bytes()?)?, diagram);
+
+        let diagram = AnimateDiagram(vec![[[None; 7]; 7], frame, [[Some('王'); 7]; 7]]);
+        let bytes = diagram.to_bytes()?;
+        assert_eq!(AnimateDiagram::from_bytes(&bytes)?, diagram);
+
+        // missing end frame
+        let mut invalid = bytes[..bytes.len() - 1].to_vec();
+        invalid.drain(..invalid.len() - 7);
+        invalid.push(sha256::Hash::hash(&invalid).as_byte_array()[0]);
+        let r = AnimateDiagram::from_bytes(&invalid);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+
+        // simple diagram version
+        let sdm = crate::SimpleDiagram(frame);
+        let r = AnimateDiagram::from_bytes(&sdm.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        Ok(())
+    }
 }
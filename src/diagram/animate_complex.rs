@@ -0,0 +1,208 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Animate Complex Diagram secret data construction
+ *      (diagram version == 6)
+ *      |-----m segments----|-m bytes-|----n*7 bytes----|-1 byte-|
+ *      |String1|String2|...|N1|N2|...| n Frame Indices |CheckSum|
+ *      |-------->>>--------|--->>>---|-------<<<-------|--------|
+ *      frame order from right to left, until end frame.
+ *      m = indices.count_ones() - n - 1
+ *      N1,N2... is bytes count of String1,String2...
+ *      strings order from end frame to first frame, same as chars of animate diagram.
+ *
+ * [2] - Animate Complex Diagram frame indices data construction
+ *      0b0xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b1xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b0xxx_xxxx
+ *      x bits indices string position in diagram.
+ *
+ * [3] - Animate Complex Diagram end frame indices data construction
+ *      0b1xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b1xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b0xxx_xxxx
+ *      0b0xxx_xxxx
+ *      x bits indices string position in diagram.
+**/
+use super::{Error, GenericDiagram, Result, split_strings, unpack_cells, verify_checksum};
+use crate::secret::ImpSecret;
+use bitcoin::hashes::{Hash, sha256};
+
+/// Animate Complex Diagram
+///
+/// Frames of 7 * 7 string cells, for multi-step stories with words in each step.
+/// Empty strings are ignored, same as `ComplexDiagram`.
+///
+/// # Examples
+/// ```
+/// # use artimonist::{AnimateComplexDiagram, GenericDiagram};
+/// let mut frames: Vec<[[Option<String>; 7]; 7]> = vec![Default::default(); 2];
+/// frames[0][1][1] = Some("wake up".to_owned());
+/// frames[1][5][5] = Some("☕".to_owned());
+/// let diagram = AnimateComplexDiagram(frames);
+///
+/// let data = diagram.to_bytes()?;
+/// assert_eq!(AnimateComplexDiagram::from_bytes(&data)?, diagram);
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+#[derive(Clone, PartialEq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
+pub struct AnimateComplexDiagram(pub Vec<[[Option<String>; 7]; 7]>);
+ImpSecret!(AnimateComplexDiagram, 0);
+
+impl GenericDiagram for AnimateComplexDiagram {
+    type Item = String;
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        if self.0.is_empty() {
+            return Err(Error::InvalidData("empty frames"));
+        }
+
+        let mut str_list: Vec<&str> = vec![];
+        let mut str_lens: Vec<u8> = vec![];
+        let mut frames = Vec::new();
+        for mx in self.0.iter().rev() {
+            let mut indices = [0_u8; 7];
+            for col in (0..7).rev() {
+                for row in (0..7).rev() {
+                    if let Some(s) = &mx[row][col]
+                        && !s.is_empty()
+                    {
+                        if s.len() > u8::MAX as usize {
+                            return Err(Error::StringTooLong(s.to_string()));
+                        }
+                        str_list.push(s);
+                        str_lens.push(s.len() as u8);
+                        indices[row] |= 1 << (6 - col);
+                    }
+                }
+            }
+            indices[2] |= 1 << 7; // version number of animate complex diagram
+            frames.push(indices);
+        }
+        frames[0][0] |= 1 << 7; // end frame of animate complex diagram
+
+        let mut secret = [str_list.join("").as_bytes(), &str_lens, &frames.concat()].concat();
+        let check = sha256::Hash::hash(&secret).as_byte_array()[0];
+        secret.push(check);
+        Ok(secret)
+    }
+}
+
+impl AnimateComplexDiagram {
+    /// cell chars count limit
+    pub const CELL_CHARS_LIMIT: usize = 50;
+
+    /// Deserialize animate complex diagram from secret data of `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut data = verify_checksum(data)?;
+
+        // frame indices from right to left, until end frame
+        let mut frames = vec![];
+        loop {
+            let (rest, indices) = data
+                .split_last_chunk::<7>()
+                .ok_or(Error::InvalidData("data too short"))?;
+            if indices[2] & 0x80 == 0
+                || indices[1] & 0x80 != 0
+                || indices[3..].iter().any(|v| v & 0x80 != 0)
+            {
+                return Err(Error::InvalidVersion);
+            }
+            let mut indices = *indices;
+            let end_frame = indices[0] & 0x80 != 0;
+            indices[0] &= 0x7f;
+            indices[2] &= 0x7f;
+            frames.push(indices);
+            data = rest;
+            if end_frame {
+                break;
+            }
+        }
+
+        // strings order from end frame to first frame
+        let count = frames
+            .iter()
+            .flatten()
+            .map(|v| v.count_ones() as usize)
+            .sum();
+        let mut str_list = split_strings(data, count)?.into_iter();
+        let mut cube = frames
+            .iter()
+            .rev()
+            .map(|indices| unpack_cells(indices, &mut str_list))
+            .collect::<Result<Vec<_>>>()?;
+        cube.reverse();
+        Ok(Self(cube))
+    }
+}
+
+#[cfg(test)]
+mod animate_complex_test {
+    use super::*;
+    use bitcoin::hex::DisplayHex;
+
+    #[test]
+    fn test_animate_complex() -> Result<()> {
+        const SECRET_HEX: &str = "e6b7b7413141262ae78e8bf09f988a414243e6b58be8af95050a030680008000000060000080000000000120800000000026";
+        let mut frames: Vec<[[Option<String>; 7]; 7]> = vec![Default::default(); 3];
+        frames[0][0][6] = Some("ABC".to_owned());
+        frames[0][1][1] = Some("测试".to_owned());
+        frames[1][3][3] = Some(String::new());
+        frames[2][6][0] = Some("A&*王😊".to_owned());
+        frames[2][6][1] = Some("混A1".to_owned());
+        let diagram = AnimateComplexDiagram(frames);
+
+        let bytes = diagram.to_bytes()?;
+        assert_eq!(bytes.to_lower_hex_string(), SECRET_HEX);
+
+        // empty strings are ignored
+        let mut expected = diagram.clone();
+        expected.0[1][3][3] = None;
+        assert_eq!(AnimateComplexDiagram::from_bytes(&bytes)?, expected);
+
+        #[cfg(not(feature = "testnet"))]
+        {
+            let master = "xprv9s21ZrQH143K4CKN4RWhUi8oVf14tEwQkAUn42SRAgxfVwxMmzA3kTMvz5vc5ZRtEntSyPBpEph3n1NWQ1YNrwZ1sFtiaBRxKe9cRuse59r";
+            assert_eq!(diagram.to_master("123abc".as_bytes())?.to_string(), master);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_animate_complex_from_bytes() -> Result<()> {
+        let mut frame: [[Option<String>; 7]; 7] = Default::default();
+        frame[0][0] = Some("satoshi".to_owned());
+        let diagram = AnimateComplexDiagram(vec![frame.clone(), Default::default(), frame]);
+        let bytes = diagram.to_bytes()?;
+        assert_eq!(AnimateComplexDiagram::from_bytes(&bytes)?, diagram);
+
+        // other diagram versions
+        let cdm = crate::ComplexDiagram(diagram.0[0].clone());
+        let r = AnimateComplexDiagram::from_bytes(&cdm.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        let r = crate::ComplexDiagram::<7, 7>::from_bytes(&bytes);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        let r = crate::AnimateDiagram::from_bytes(&bytes);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        let r = crate::SimpleDiagram::<7, 7>::from_bytes(&bytes);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+
+        // strings length mismatch
+        let mut invalid = bytes[1..bytes.len() - 1].to_vec();
+        invalid.push(sha256::Hash::hash(&invalid).as_byte_array()[0]);
+        let r = AnimateComplexDiagram::from_bytes(&invalid);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+
+        let r = AnimateComplexDiagram(vec![]).to_bytes();
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+        Ok(())
+    }
+}
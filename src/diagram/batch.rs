@@ -0,0 +1,146 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Batch derivation
+ *      items are shared by worker threads, each worker takes the next item until all done.
+ *      results are in the same order as items.
+ *
+ * [2] - Memory
+ *      each worker thread derives one item at a time, using `KdfParams::memory_cost` bytes,
+ *      about 1 GiB for V2 parameters. threads count is never more than requested,
+ *      callers choose it by available memory, default 0 is one thread.
+**/
+use super::{GenericDiagram, KdfParams, Result};
+use bitcoin::bip32::Xpriv;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Derive diagrams in parallel
+///
+/// Implemented for slices of (diagram, salt) pairs.
+/// `threads` is the worker threads count, 0 means one thread.
+/// Each thread uses `params.memory_cost()` bytes, about 1 GiB for `KdfParams::V2`,
+/// so choose threads count by available memory, not only by CPU cores.
+///
+/// # Examples
+/// ```
+/// use artimonist::{BatchDiagram, GenericDiagram, KdfParams, SimpleDiagram};
+///
+/// let params = KdfParams {
+///     scrypt_log_n: 10,
+///     argon2_m_cost: 1024,
+///     ..KdfParams::V2
+/// };
+/// let mut mx = [[None; 7]; 7];
+/// mx[3][3] = Some('🍩');
+/// let items = [(SimpleDiagram(mx), "🚲"), (SimpleDiagram(mx), "🍀")];
+///
+/// let masters = items.batch_master(&params, 2);
+/// assert_eq!(masters[1].as_ref().unwrap(), &items[1].0.to_master_with("🍀".as_bytes(), &params)?);
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+pub trait BatchDiagram {
+    /// generate warp entropy of all items
+    fn batch_entropy(&self, params: &KdfParams, threads: usize) -> Vec<Result<[u8; 32]>>;
+
+    /// generate extended private keys of all items
+    fn batch_master(&self, params: &KdfParams, threads: usize) -> Vec<Result<Xpriv>>;
+}
+
+impl<D, S> BatchDiagram for [(D, S)]
+where
+    D: GenericDiagram + Sync,
+    S: AsRef<[u8]> + Sync,
+{
+    fn batch_entropy(&self, params: &KdfParams, threads: usize) -> Vec<Result<[u8; 32]>> {
+        run_batch(self.len(), threads, |i| {
+            let (diagram, salt) = &self[i];
+            diagram.to_entropy_with(salt.as_ref(), params)
+        })
+    }
+
+    fn batch_master(&self, params: &KdfParams, threads: usize) -> Vec<Result<Xpriv>> {
+        run_batch(self.len(), threads, |i| {
+            let (diagram, salt) = &self[i];
+            diagram.to_master_with(salt.as_ref(), params)
+        })
+    }
+}
+
+/// Run `f` for indices 0..count in worker threads
+fn run_batch<T: Send>(count: usize, threads: usize, f: impl Fn(usize) -> T + Sync) -> Vec<T> {
+    // 0 means one thread, not more than tasks count
+    let threads = threads.max(1).min(count);
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<T>> = (0..count).map(|_| None).collect();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= count {
+                            break done;
+                        }
+                        done.push((i, f(i)));
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+            done.into_iter().for_each(|(i, v)| results[i] = Some(v));
+        }
+    });
+    results.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod batch_test {
+    use super::*;
+    use crate::{ComplexDiagram, SimpleDiagram};
+
+    #[test]
+    fn test_batch() -> Result<()> {
+        let params = KdfParams {
+            scrypt_log_n: 10,
+            argon2_m_cost: 256,
+            ..KdfParams::V2
+        };
+        let items: Vec<_> = (0..5)
+            .map(|i| {
+                let mut mx = [[None; 7]; 7];
+                mx[i][i] = Some('🍩');
+                (SimpleDiagram(mx), format!("salt {i}"))
+            })
+            .collect();
+
+        for threads in [0, 1, 3, 8] {
+            let results = items.batch_entropy(&params, threads);
+            assert_eq!(results.len(), items.len());
+            for ((diagram, salt), entropy) in items.iter().zip(results) {
+                assert_eq!(entropy?, diagram.to_entropy_with(salt.as_bytes(), &params)?);
+            }
+        }
+
+        let params_v1 = KdfParams {
+            scrypt_log_n: 10,
+            pbkdf2_rounds: Some(1024),
+            ..KdfParams::V1
+        };
+        let mut cdm = ComplexDiagram::<7, 7>::default();
+        cdm[1][1] = Some("ABC".to_owned());
+        let items = [(cdm.clone(), b"123abc".as_slice()), (cdm, &[])];
+        let masters = items.batch_master(&params_v1, 0);
+        for ((diagram, salt), master) in items.iter().zip(masters) {
+            assert_eq!(master?, diagram.to_master_with(salt, &params_v1)?);
+        }
+
+        let empty: [(SimpleDiagram, &str); 0] = [];
+        assert!(empty.batch_master(&params, 0).is_empty());
+        Ok(())
+    }
+}
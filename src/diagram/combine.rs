@@ -0,0 +1,350 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Combined entropy of N diagrams
+ *      e1, e2, ... en = to_entropy_with(diagram_i, salt_i)
+ *      Xor:  entropy = e1 ⊕ e2 ⊕ ... ⊕ en
+ *      Hash: entropy = sha256("artimonist combined diagrams" || sorted(e1, e2, ... en))
+ *      both combiners don't depend on diagrams order.
+ *      equal entropies are rejected, they cancel each other out by xor.
+ *
+ * [2] - M-of-N secret sharing of combined entropy
+ *      Shamir's secret sharing over GF(2^8), reduction polynomial x^8 + x^4 + x^3 + x + 1.
+ *      each byte of entropy is the constant term of a random polynomial of degree M - 1,
+ *      share i is (i, f(i)) of all bytes, 1 <= i <= N.
+ *      any M shares recover the entropy by Lagrange interpolation at x = 0.
+ *      shares have no checksum, wrong shares recover a wrong entropy silently,
+ *      compare the master fingerprint to confirm.
+**/
+use super::{Error, GenericDiagram, KdfParams, Result};
+use crate::secret::{ImpSecret, protect};
+use bitcoin::Network;
+use bitcoin::bip32::Xpriv;
+use bitcoin::hashes::{Hash, HashEngine, sha256};
+use rand::RngCore;
+
+const HASH_TAG: &[u8] = b"artimonist combined diagrams";
+
+/// Entropy combiner of diagrams
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Combiner {
+    /// Xor entropies, same as branches of `to_entropy`
+    #[default]
+    Xor,
+    /// Hash sorted entropies with SHA256
+    Hash,
+}
+
+impl Combiner {
+    /// Combine entropies of diagrams
+    pub fn combine(&self, entropies: &[[u8; 32]]) -> Result<[u8; 32]> {
+        let mut sorted = protect(entropies.to_vec());
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != entropies.len() {
+            return Err(Error::InvalidData("duplicate entropy"));
+        }
+        if sorted.is_empty() {
+            return Err(Error::InvalidData("empty entropies"));
+        }
+
+        match self {
+            Combiner::Xor => Ok(sorted.iter().fold([0; 32], |mut acc, e| {
+                acc.iter_mut().zip(e).for_each(|(a, b)| *a ^= b);
+                acc
+            })),
+            Combiner::Hash => {
+                let mut engine = sha256::Hash::engine();
+                engine.input(HASH_TAG);
+                sorted.iter().for_each(|e| engine.input(e));
+                Ok(sha256::Hash::from_engine(engine).to_byte_array())
+            }
+        }
+    }
+}
+
+/// Combine several diagrams into one master
+///
+/// Implemented for slices of (diagram, salt) pairs, each person remembers one diagram.
+///
+/// # Examples
+/// ```
+/// use artimonist::{CombineDiagram, Combiner, GenericDiagram, KdfParams, Share, SimpleDiagram};
+///
+/// let params = KdfParams {
+///     scrypt_log_n: 10,
+///     argon2_m_cost: 256,
+///     ..KdfParams::V2
+/// };
+/// let (mut alice, mut bob) = ([[None; 7]; 7], [[None; 7]; 7]);
+/// alice[1][1] = Some('🍔');
+/// bob[5][5] = Some('🌭');
+/// let diagrams = [(SimpleDiagram(alice), "🚲"), (SimpleDiagram(bob), "🍀")];
+/// let entropy = diagrams.combine_entropy(Combiner::Xor, &params)?;
+///
+/// // 2-of-3 shares of combined entropy
+/// let shares = Share::split(&entropy, 2, 3)?;
+/// assert_eq!(Share::recover(&shares[1..])?, entropy);
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+pub trait CombineDiagram {
+    /// generate combined entropy of all diagrams
+    fn combine_entropy(&self, combiner: Combiner, params: &KdfParams) -> Result<[u8; 32]>;
+
+    /// generate extended private key of combined entropy
+    fn combine_master(&self, combiner: Combiner, params: &KdfParams) -> Result<Xpriv> {
+        self.combine_master_in(combiner, params, crate::DEFAULT_NETWORK)
+    }
+
+    /// generate extended private key of combined entropy for network
+    fn combine_master_in(
+        &self,
+        combiner: Combiner,
+        params: &KdfParams,
+        network: Network,
+    ) -> Result<Xpriv> {
+        let seed = protect(self.combine_entropy(combiner, params)?);
+        Ok(Xpriv::new_master(network, &seed[..])?)
+    }
+}
+
+impl<D, S> CombineDiagram for [(D, S)]
+where
+    D: GenericDiagram,
+    S: AsRef<[u8]>,
+{
+    fn combine_entropy(&self, combiner: Combiner, params: &KdfParams) -> Result<[u8; 32]> {
+        let entropies = protect(
+            self.iter()
+                .map(|(diagram, salt)| diagram.to_entropy_with(salt.as_ref(), params))
+                .collect::<Result<Vec<_>>>()?,
+        );
+        combiner.combine(&entropies)
+    }
+}
+
+/// Secret share of combined entropy
+///
+/// `data` is 32 bytes, it can be written down as 24 words by `Mnemonic::new`.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
+pub struct Share {
+    /// Share index, 1 <= index <= N
+    pub index: u8,
+    /// Share data
+    pub data: [u8; 32],
+}
+ImpSecret!(Share, data);
+
+impl Share {
+    /// Split entropy into `count` shares, any `threshold` shares recover it.
+    ///
+    /// 1 <= threshold <= count <= 255
+    pub fn split(entropy: &[u8; 32], threshold: u8, count: u8) -> Result<Vec<Share>> {
+        if threshold == 0 || threshold > count {
+            return Err(Error::InvalidData("share threshold"));
+        }
+        let mut coefficients = protect(vec![[0u8; 32]; threshold as usize - 1]);
+        coefficients
+            .iter_mut()
+            .for_each(|c| rand::thread_rng().fill_bytes(c));
+        Ok(split_with(entropy, &coefficients, count))
+    }
+
+    /// Recover entropy from shares
+    pub fn recover(shares: &[Share]) -> Result<[u8; 32]> {
+        if shares.is_empty() {
+            return Err(Error::InvalidData("empty shares"));
+        }
+        let mut indices: Vec<u8> = shares.iter().map(|s| s.index).collect();
+        indices.sort();
+        indices.dedup();
+        if indices.len() != shares.len() || indices[0] == 0 {
+            return Err(Error::InvalidData("share index"));
+        }
+
+        // Lagrange basis at x = 0: l_i = ∏ x_j / (x_j - x_i), subtraction is xor in GF(2^8)
+        let mut entropy = [0u8; 32];
+        for share in shares {
+            let basis = shares
+                .iter()
+                .filter(|s| s.index != share.index)
+                .fold(1, |acc, s| {
+                    gf_mul(acc, gf_mul(s.index, gf_inv(s.index ^ share.index)))
+                });
+            entropy
+                .iter_mut()
+                .zip(share.data)
+                .for_each(|(e, v)| *e ^= gf_mul(basis, v));
+        }
+        Ok(entropy)
+    }
+}
+
+/// Split entropy with coefficients of polynomial terms x^1, x^2, ...
+fn split_with(entropy: &[u8; 32], coefficients: &[[u8; 32]], count: u8) -> Vec<Share> {
+    (1..=count)
+        .map(|x| {
+            // Horner's method from the highest term
+            let mut data = [0u8; 32];
+            for c in coefficients.iter().rev().chain([entropy]) {
+                data.iter_mut()
+                    .zip(c)
+                    .for_each(|(v, c)| *v = gf_mul(*v, x) ^ c);
+            }
+            Share { index: x, data }
+        })
+        .collect()
+}
+
+/// Multiplication in GF(2^8)
+/// constant time: fixed 8 rounds, bits are masks instead of branches
+fn gf_mul(mut a: u8, b: u8) -> u8 {
+    let mut product = 0;
+    for i in 0..8 {
+        product ^= a & 0u8.wrapping_sub((b >> i) & 1);
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(a >> 7));
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8), a^254
+fn gf_inv(a: u8) -> u8 {
+    (0..7)
+        .fold((1, a), |(acc, sq), _| {
+            let sq = gf_mul(sq, sq);
+            (gf_mul(acc, sq), sq)
+        })
+        .0
+}
+
+#[cfg(test)]
+mod combine_test {
+    use super::*;
+    use crate::{ComplexDiagram, SimpleDiagram};
+    use bitcoin::hex::{DisplayHex, FromHex};
+
+    #[test]
+    fn test_combiner() -> Result<()> {
+        let e1 = <[u8; 32]>::from_hex(
+            "ad1a1825c929a7381cc74e29583796ee0a00be0913b78e03a5d2ca1ae00236b6",
+        )
+        .unwrap();
+        let e2 = <[u8; 32]>::from_hex(
+            "8db994dbd6481561dd4b92740f3ba517c9fb2ee9500882e4481e0e2991cb0271",
+        )
+        .unwrap();
+
+        let xor = Combiner::Xor.combine(&[e1, e2])?;
+        assert_eq!(
+            xor.to_lower_hex_string(),
+            "20a38cfe1f61b259c18cdc5d570c33f9c3fb90e043bf0ce7edccc43371c934c7"
+        );
+        assert_eq!(Combiner::Xor.combine(&[e2, e1])?, xor);
+
+        let hash = Combiner::Hash.combine(&[e2, e1])?;
+        assert_eq!(
+            hash.to_lower_hex_string(),
+            "8dc5b29530f638bebcc3a11a3cd8caec9ab36e9bce2f28298e16cd3a9ffcaa0a"
+        );
+        assert_eq!(Combiner::Hash.combine(&[e1, e2])?, hash);
+        assert_eq!(
+            Combiner::Hash.combine(&[e1])?,
+            Combiner::Hash.combine(&[e1])?
+        );
+        assert_eq!(Combiner::Xor.combine(&[e1])?, e1);
+
+        assert!(Combiner::Xor.combine(&[e1, e2, e1]).is_err());
+        assert!(Combiner::Hash.combine(&[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_combine_diagrams() -> Result<()> {
+        let params = KdfParams {
+            scrypt_log_n: 10,
+            argon2_m_cost: 256,
+            ..KdfParams::V2
+        };
+        let mut sdm = SimpleDiagram::<7, 7>::default();
+        sdm[0][0] = Some('A');
+        let mut cdm = ComplexDiagram::<7, 7>::default();
+        cdm[0][0] = Some("A".to_owned());
+
+        // same diagram with different salts
+        let diagrams = [(sdm.clone(), "alice"), (sdm.clone(), "bob")];
+        let entropy = diagrams.combine_entropy(Combiner::Hash, &params)?;
+        let expected = Combiner::Hash.combine(&[
+            sdm.to_entropy_with(b"bob", &params)?,
+            sdm.to_entropy_with(b"alice", &params)?,
+        ])?;
+        assert_eq!(entropy, expected);
+
+        let master = diagrams.combine_master(Combiner::Hash, &params)?;
+        assert_eq!(master, Xpriv::new_master(crate::NETWORK, &entropy)?);
+        let master = diagrams.combine_master_in(Combiner::Hash, &params, Network::Regtest)?;
+        assert_eq!(master, Xpriv::new_master(Network::Regtest, &entropy)?);
+
+        // simple and complex diagrams
+        let xor = Combiner::Xor.combine(&[
+            sdm.to_entropy_with(&[], &params)?,
+            cdm.to_entropy_with(&[], &params)?,
+        ])?;
+        assert_eq!(
+            xor.to_lower_hex_string(),
+            "c0cdcd79c51365854183a5509f691f3461242a2ce0d1cc64407cd09506bb194d"
+        );
+
+        let same = [(sdm.clone(), ""), (sdm, "")];
+        let r = same.combine_entropy(Combiner::Xor, &params);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shares() -> Result<()> {
+        // GF(2^8) of AES
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert!((1..=255).all(|a| gf_mul(a, gf_inv(a)) == 1));
+
+        let entropy = [0x5a; 32];
+        let coefficients = [[0x01; 32], [0x80; 32]];
+        let shares = split_with(&entropy, &coefficients, 4);
+        assert_eq!(shares[0].data, [0x5a ^ 0x01 ^ 0x80; 32]);
+        assert_eq!(
+            shares[2].data.to_lower_hex_string(),
+            "efefefefefefefefefefefefefefefefefefefefefefefefefefefefefefefef"
+        );
+
+        // any 3 of 4 shares
+        for skip in 0..4 {
+            let mut subset = shares.clone();
+            subset.remove(skip);
+            assert_eq!(Share::recover(&subset)?, entropy);
+        }
+        assert_ne!(Share::recover(&shares[..2])?, entropy);
+
+        let shares = Share::split(&entropy, 2, 5)?;
+        assert_eq!(
+            Share::recover(&[shares[4].clone(), shares[1].clone()])?,
+            entropy
+        );
+        let shares = Share::split(&entropy, 1, 1)?;
+        assert_eq!(shares[0].data, entropy);
+
+        assert!(Share::split(&entropy, 3, 2).is_err());
+        assert!(Share::split(&entropy, 0, 2).is_err());
+        let duplicate = [shares[0].clone(), shares[0].clone()];
+        assert!(Share::recover(&duplicate).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_gf_mul() {
+        // FIPS-197 4.2
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        (1..=255).for_each(|a| assert_eq!(gf_mul(a, gf_inv(a)), 1));
+        (0..=255).for_each(|a| assert_eq!(gf_mul(a, 0), 0));
+    }
+}
@@ -24,27 +24,78 @@
  *      0b0xxx_xxxx
  *      1 bit at top left corner is version of complex diagram.
  *      others x bits indices string position in diagram.
+ *
+ * [3] - Sized Complex Diagram secret data construction
+ *      (H * W != 7 * 7, diagram version == 0x84)
+ *      |-----n segments----|-n bytes-|-ceil(H*W/8) bytes-|-1 byte-|-1 byte-|-1 byte-|-1 byte-|
+ *      |String1|String2|...|N1|N2|...|      Indices      |    H   |    W   |  0x84  |CheckSum|
+ *      |-------->>>--------|--->>>---|-------------------|--------|--------|--------|--------|
+ *      indices are row-major bits of cells, padding with zero bits.
+ *      strings are in row-major order.
 **/
-use super::{Error, GenericDiagram, Result};
-use crate::macros::ImpDeref;
+use super::validate::check_cells;
+use super::{
+    DiagramIssue, Error, GenericDiagram, Result, VERSION_SIZED_COMPLEX, sized_trailer, split_sized,
+    split_strings, unpack_cells, unpack_sized, verify_checksum,
+};
+use crate::secret::ImpSecret;
 use bitcoin::hashes::{Hash, sha256};
 
 /// Complex Diagram
 ///
-/// Complex diagram contains strings in 7 * 7 grid cells.
+/// Complex diagram contains strings in H * W grid cells, 7 * 7 by default.
 /// All UTF-8 strings with less than 50 characters are supported.
+/// Height and width must be in range 1..=255.
 ///
-#[derive(Debug, Default, Clone, PartialEq)]
-pub struct ComplexDiagram(pub [[Option<String>; 7]; 7]);
-ImpDeref!(ComplexDiagram, [[Option<String>; 7]; 7]);
+#[derive(Clone, PartialEq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
+pub struct ComplexDiagram<const H: usize = 7, const W: usize = 7>(pub [[Option<String>; W]; H]);
+ImpSecret!(ComplexDiagram<const H, const W>, 0);
+
+impl<const H: usize, const W: usize> Default for ComplexDiagram<H, W> {
+    fn default() -> Self {
+        Self(core::array::from_fn(|_| core::array::from_fn(|_| None)))
+    }
+}
+
+impl<const H: usize, const W: usize> std::ops::Deref for ComplexDiagram<H, W> {
+    type Target = [[Option<String>; W]; H];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const H: usize, const W: usize> std::ops::DerefMut for ComplexDiagram<H, W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
 
-impl GenericDiagram for ComplexDiagram {
+impl<const H: usize, const W: usize> GenericDiagram for ComplexDiagram<H, W> {
     type Item = String;
 
     /// Compatible with previous versions
     fn to_bytes(&self) -> Result<Vec<u8>> {
         let mut str_list: Vec<&str> = vec![];
         let mut str_lens: Vec<u8> = vec![];
+
+        if (H, W) != (7, 7) {
+            let is_filled = |v: &Option<String>| v.as_ref().is_some_and(|s| !s.is_empty());
+            for s in self.iter().flatten().filter(|v| is_filled(v)).flatten() {
+                if s.len() > u8::MAX as usize {
+                    return Err(Error::StringTooLong(s.to_string()));
+                }
+                str_list.push(s);
+                str_lens.push(s.len() as u8);
+            }
+            let cells = self.iter().flatten().map(is_filled);
+            let trailer = sized_trailer::<H, W>(cells, VERSION_SIZED_COMPLEX)?;
+            let mut secret = [str_list.join("").as_bytes(), &str_lens[..], &trailer].concat();
+            let check = sha256::Hash::hash(&secret).as_byte_array()[0];
+            secret.push(check);
+            return Ok(secret);
+        }
+
         let mut indices: [u8; 7] = [0; 7];
 
         (0..7).rev().try_for_each(|col| {
@@ -71,10 +122,43 @@ impl GenericDiagram for ComplexDiagram {
     }
 }
 
-impl ComplexDiagram {
+impl<const H: usize, const W: usize> ComplexDiagram<H, W> {
     /// cell chars count limit
     pub const CELL_CHARS_LIMIT: usize = 50;
 
+    /// Deserialize complex diagram from secret data of `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let payload = verify_checksum(data)?;
+        if (H, W) != (7, 7) {
+            let (data, cells) = split_sized::<H, W>(payload, VERSION_SIZED_COMPLEX)?;
+            let count = cells.iter().filter(|&&v| v).count();
+            let str_list = split_strings(data, count)?;
+            let mx = unpack_sized(&cells, &mut str_list.into_iter())?;
+            return Ok(Self(mx));
+        }
+
+        let (data, indices) = payload
+            .split_last_chunk::<7>()
+            .ok_or(Error::InvalidData("data too short"))?;
+        if indices[0] & 0x80 == 0 || indices[1..].iter().any(|v| v & 0x80 != 0) {
+            return Err(Error::InvalidVersion);
+        }
+        let mut indices = *indices;
+        indices[0] &= 0x7f;
+
+        let count: usize = indices.iter().map(|v| v.count_ones() as usize).sum();
+        let str_list = split_strings(data, count)?;
+        let mx = unpack_cells(&indices, &mut str_list.into_iter())?;
+        Ok(Self(mx))
+    }
+
+    /// Check issues of diagram cells, index = row * W + col
+    pub fn validate(&self) -> Vec<DiagramIssue> {
+        check_cells(self.iter().flatten().map(Option::as_ref))
+    }
+}
+
+impl ComplexDiagram {
     /// create complex diagram
     #[deprecated(since = "1.7.2", note = "Use `Diagram` instead")]
     pub fn new() -> Self {
@@ -157,4 +241,88 @@ mod complex_diagram_test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_complex_from_bytes() -> Result<()> {
+        use bitcoin::hex::FromHex;
+        const STR_LIST: &[&str] = &["ABC", "混A1", "123", "测试", "A&*王😊"];
+        const INDICES: &[(usize, usize)] = &[(0, 6), (1, 1), (1, 3), (4, 2), (6, 0)];
+        const SECRET_HEX: &str =
+            "414243313233e6b58be8af95e6b7b7413141262ae78e8bf09f988a030306050a8128000010004052";
+
+        let data = Vec::from_hex(SECRET_HEX).unwrap();
+        let cdm = ComplexDiagram::from_values(STR_LIST, INDICES);
+        assert_eq!(ComplexDiagram::from_bytes(&data)?, cdm);
+
+        let empty = ComplexDiagram::new();
+        assert_eq!(ComplexDiagram::from_bytes(&empty.to_bytes()?)?, empty);
+
+        // invalid checksum
+        let mut invalid = data.clone();
+        invalid[0] = b'B';
+        let r: Result<ComplexDiagram> = ComplexDiagram::from_bytes(&invalid);
+        assert!(matches!(r, Err(Error::InvalidChecksum)));
+
+        // simple diagram version
+        let sdm = crate::SimpleDiagram::from_values(&['A'], &[(0, 0)]);
+        let r: Result<ComplexDiagram> = ComplexDiagram::from_bytes(&sdm.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+
+        // strings length mismatch
+        let mut invalid = data[1..data.len() - 1].to_vec();
+        invalid.push(sha256::Hash::hash(&invalid).as_byte_array()[0]);
+        let r: Result<ComplexDiagram> = ComplexDiagram::from_bytes(&invalid);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sized_complex_diagram() -> Result<()> {
+        const SECRET_5X5: &str = "414243e6b58be8af9541262ae78e8bf09f988a03060a080088000505842a";
+        const SECRET_9X9: &str = "313233e6b7b7413103058000000000000000000080090984d7";
+        const SECRET_12X12: &str =
+            "7361746f736869f09f8da9f09f8da907080000000000000000100000000000000008000c0c840d";
+
+        let mut cdm5 = ComplexDiagram::<5, 5>::default();
+        cdm5[0][4] = Some("ABC".to_owned());
+        cdm5[3][1] = Some("测试".to_owned());
+        cdm5[4][0] = Some("A&*王😊".to_owned());
+        assert_eq!(cdm5.to_bytes()?.to_lower_hex_string(), SECRET_5X5);
+        assert_eq!(ComplexDiagram::<5, 5>::from_bytes(&cdm5.to_bytes()?)?, cdm5);
+
+        let mut cdm9 = ComplexDiagram::<9, 9>::default();
+        cdm9[0][0] = Some("123".to_owned());
+        cdm9[8][8] = Some("混A1".to_owned());
+        assert_eq!(cdm9.to_bytes()?.to_lower_hex_string(), SECRET_9X9);
+        assert_eq!(ComplexDiagram::<9, 9>::from_bytes(&cdm9.to_bytes()?)?, cdm9);
+
+        // empty strings are ignored
+        let mut cdm12 = ComplexDiagram::<12, 12>::default();
+        cdm12[5][7] = Some("satoshi".to_owned());
+        cdm12[11][0] = Some("🍩🍩".to_owned());
+        cdm12[2][2] = Some(String::new());
+        assert_eq!(cdm12.to_bytes()?.to_lower_hex_string(), SECRET_12X12);
+        cdm12[2][2] = None;
+        assert_eq!(
+            ComplexDiagram::<12, 12>::from_bytes(&cdm12.to_bytes()?)?,
+            cdm12
+        );
+
+        #[cfg(not(feature = "testnet"))]
+        {
+            let master = "xprv9s21ZrQH143K3tXUBk2BrvpABByp5bhd1Xmk9VW5SDgGaAQrExpK19J5RCqbatK1sVLBvCdADi4DYH8fYsJsziaFbuZyAdxeWnV99FtMdkM";
+            assert_eq!(cdm9.to_master("123abc".as_bytes())?.to_string(), master);
+        }
+
+        // size mismatch
+        let r = ComplexDiagram::<12, 12>::from_bytes(&cdm9.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+
+        // legacy and sized versions are not interchangeable
+        let r: Result<ComplexDiagram> = ComplexDiagram::from_bytes(&cdm5.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        let r = ComplexDiagram::<5, 5>::from_bytes(&ComplexDiagram::new().to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        Ok(())
+    }
 }
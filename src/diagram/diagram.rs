@@ -1,6 +1,8 @@
-use super::{ComplexDiagram, GenericDiagram, Result, SimpleDiagram};
+use super::validate::check_inputs;
+use super::{ComplexDiagram, DiagramIssue, GenericDiagram, GraphemeDiagram, Result, SimpleDiagram};
 use bitcoin::bip32::Xpriv;
 use std::ops::Not;
+use unicode_normalization::UnicodeNormalization;
 
 /// Diagram
 pub trait Diagram {
@@ -18,6 +20,17 @@ pub trait Diagram {
 
     /// Complex diagram (7 * 7 unicode strings) generate master key
     fn art_complex_master(&self, salt: &str) -> Result<Xpriv>;
+
+    /// Grapheme diagram (7 * 7 NFC normalized grapheme clusters)
+    fn art_grapheme_diagram(&self) -> Result<GraphemeDiagram>;
+
+    /// Grapheme diagram (7 * 7 NFC normalized grapheme clusters) generate master key
+    ///   salt is NFC normalized too.
+    fn art_grapheme_master(&self, salt: &str) -> Result<Xpriv>;
+
+    /// Check inputs truncated, overflowed, not normalized, invisible or confusable
+    ///   the first 98 inputs are checked.
+    fn art_validate(&self) -> Vec<DiagramIssue>;
 }
 
 impl<T, U> Diagram for U
@@ -42,6 +55,14 @@ where
         Ok(ComplexDiagram(mx))
     }
 
+    fn art_grapheme_diagram(&self) -> Result<GraphemeDiagram> {
+        let mut mx = std::array::from_fn(|_| std::array::from_fn(|_| None));
+        self.clone().take(7 * 7).enumerate().for_each(|(i, s)| {
+            mx[i / 7][i % 7] = GraphemeDiagram::normalize(s.as_ref());
+        });
+        Ok(GraphemeDiagram(mx))
+    }
+
     #[inline]
     fn art_simple_master(&self, salt: &str) -> Result<Xpriv> {
         self.art_simple_diagram()?.to_master(salt.as_bytes())
@@ -51,6 +72,17 @@ where
     fn art_complex_master(&self, salt: &str) -> Result<Xpriv> {
         self.art_complex_diagram()?.to_master(salt.as_bytes())
     }
+
+    #[inline]
+    fn art_grapheme_master(&self, salt: &str) -> Result<Xpriv> {
+        let salt: String = salt.nfc().collect();
+        self.art_grapheme_diagram()?.to_master(salt.as_bytes())
+    }
+
+    #[inline]
+    fn art_validate(&self) -> Vec<DiagramIssue> {
+        check_inputs(self.clone())
+    }
 }
 
 impl<T> Diagram for [T]
@@ -76,6 +108,21 @@ where
     fn art_complex_master(&self, salt: &str) -> Result<Xpriv> {
         self.iter().art_complex_master(salt)
     }
+
+    #[inline(always)]
+    fn art_grapheme_diagram(&self) -> Result<GraphemeDiagram> {
+        self.iter().art_grapheme_diagram()
+    }
+
+    #[inline(always)]
+    fn art_grapheme_master(&self, salt: &str) -> Result<Xpriv> {
+        self.iter().art_grapheme_master(salt)
+    }
+
+    #[inline(always)]
+    fn art_validate(&self) -> Vec<DiagramIssue> {
+        check_inputs(self.iter())
+    }
 }
 
 #[cfg(test)]
@@ -126,4 +173,96 @@ mod diagram_test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_grapheme_master() -> Result<()> {
+        use crate::DiagramIssue;
+
+        // NFC and NFD inputs
+        let nfc = ["🇺🇸", "", "Åb", "👋🏽", "", "", "é"];
+        let nfd = ["🇺🇸", "", "A\u{30a}b", "👋🏽", "", "", "e\u{301}"];
+        let master = "xprv9s21ZrQH143K3xK7YU8twQwDDhKcETWfGDzfjAeLDNMUfd38s87hRTuEZksao5h8TJs6YNSEpkwdrihS8vFfaCWncX1spP3EYFvwhgitxFQ";
+        assert_eq!(nfc.art_grapheme_master("café")?.to_string(), master);
+        assert_eq!(nfd.art_grapheme_master("cafe\u{301}")?.to_string(), master);
+        assert_ne!(nfc.art_complex_master("")?, nfd.art_complex_master("")?);
+
+        assert!(nfc.art_grapheme_diagram()?[0][2] == Some("Å".to_owned()));
+        assert_eq!(
+            nfc.art_validate(),
+            [
+                DiagramIssue::CharTruncated {
+                    index: 0,
+                    kept: '🇺'
+                },
+                DiagramIssue::CharTruncated {
+                    index: 2,
+                    kept: 'Å'
+                },
+                DiagramIssue::GraphemeTruncated {
+                    index: 2,
+                    kept: "Å".to_owned()
+                },
+                DiagramIssue::CharTruncated {
+                    index: 3,
+                    kept: '👋'
+                },
+            ]
+        );
+        assert_eq!(
+            nfd.art_validate()[2..],
+            [
+                DiagramIssue::GraphemeTruncated {
+                    index: 2,
+                    kept: "Å".to_owned()
+                },
+                DiagramIssue::Normalized {
+                    index: 2,
+                    normalized: "Åb".to_owned()
+                },
+                DiagramIssue::CharTruncated {
+                    index: 3,
+                    kept: '👋'
+                },
+                DiagramIssue::CharTruncated {
+                    index: 6,
+                    kept: 'e'
+                },
+                DiagramIssue::Normalized {
+                    index: 6,
+                    normalized: "é".to_owned()
+                },
+            ]
+        );
+
+        let long = ["x".repeat(60)];
+        assert!(
+            long.art_validate()
+                .contains(&DiagramIssue::StringTruncated {
+                    index: 0,
+                    kept: "x".repeat(50)
+                })
+        );
+
+        // iterators and slices report the same overflow
+        let issues = ["a"; 52].art_validate();
+        assert_eq!(issues, [DiagramIssue::Overflow { ignored: 3 }]);
+        assert_eq!(["a"; 52].iter().art_validate(), issues);
+        let mut inputs = vec!["a"; 51];
+        inputs[49] = "";
+        assert_eq!(
+            inputs.art_validate(),
+            [DiagramIssue::Overflow { ignored: 1 }]
+        );
+        assert_eq!(
+            inputs.iter().art_validate(),
+            [DiagramIssue::Overflow { ignored: 1 }]
+        );
+
+        // inputs beyond 98 are not checked, iterators may be endless
+        let issues = std::iter::repeat("a").art_validate();
+        assert_eq!(issues, [DiagramIssue::Overflow { ignored: 49 }]);
+        let issues = ["a"; 200].art_validate();
+        assert_eq!(issues, [DiagramIssue::Overflow { ignored: 49 }]);
+        Ok(())
+    }
 }
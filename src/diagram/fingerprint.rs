@@ -0,0 +1,153 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Diagram fingerprint
+ *      HMAC-SHA256(key = pepper, message = to_bytes), no key stretching.
+ *      pepper is a secret of the user or device (at least 16 bytes), kept apart from the fingerprint,
+ *      without it a displayed fingerprint is no shortcut to brute force the diagram.
+ *      only the first 24 bits (emoji) or 33 bits (words) are displayed,
+ *      many diagrams share one fingerprint, it confirms typing but can't identify a diagram.
+ *
+ * [2] - Master fingerprint
+ *      BIP32 fingerprint of master key, 32 bits, padding zero bits for words.
+ *
+ * [3] - Display
+ *      emoji: 4 chars of `Password::Emoji` table, 6 bits each.
+ *      words: 3 words of BIP39 wordlist, 11 bits each.
+**/
+use super::{Error, GenericDiagram, Result};
+use crate::{Language, Password};
+use bitcoin::bip32::Xpriv;
+use bitcoin::hashes::{Hash, HashEngine, hmac, sha256};
+use bitcoin::key::Secp256k1;
+use xbits::XBits;
+
+/// Minimum length of fingerprint pepper
+const PEPPER_MIN_LEN: usize = 16;
+
+/// Fingerprint display style
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FingerprintStyle {
+    /// 4 emoji chars
+    #[default]
+    Emoji,
+    /// 3 BIP39 words
+    Words(Language),
+}
+
+impl FingerprintStyle {
+    /// Display leading bits of data, data is padded with zero bits
+    pub fn display(&self, data: &[u8]) -> String {
+        let data = [data, &[0; 5]].concat();
+        match self {
+            Self::Emoji => data
+                .bits()
+                .chunks(6)
+                .take(4)
+                .map(|i: usize| Password::Emoji.char_at(i))
+                .collect(),
+            Self::Words(language) => data
+                .bits()
+                .chunks(11)
+                .take(3)
+                .map(|i: usize| language.word_at(i).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// Display BIP32 fingerprint of master key
+    ///
+    /// # Examples
+    /// ```
+    /// # use artimonist::{FingerprintStyle, GenericDiagram, KdfParams, Language, SimpleDiagram};
+    /// # let params = KdfParams { scrypt_log_n: 10, argon2_m_cost: 256, ..KdfParams::V2 };
+    /// let mut mx = [[None; 7]; 7];
+    /// mx[3][3] = Some('🍩');
+    /// let diagram = SimpleDiagram(mx);
+    ///
+    /// // fast check while typing, keyed by a secret of this device,
+    /// // then check master after key stretching
+    /// let pepper = [0x5a; 32];
+    /// assert_eq!(diagram.fingerprint(&pepper, FingerprintStyle::Emoji)?, "☔💊❤💀");
+    /// let master = diagram.to_master_with(b"salt", &params)?;
+    /// let words = FingerprintStyle::Words(Language::English).master(&master);
+    /// assert_eq!(words.split(' ').count(), 3);
+    /// # Ok::<(), artimonist::Error>(())
+    /// ```
+    pub fn master(&self, master: &Xpriv) -> String {
+        let fingerprint = master.fingerprint(&Secp256k1::signing_only());
+        self.display(fingerprint.as_bytes())
+    }
+}
+
+/// Fast fingerprint of diagram data, keyed by secret pepper
+pub(super) fn fingerprint(
+    diagram: &(impl GenericDiagram + ?Sized),
+    pepper: &[u8],
+    style: FingerprintStyle,
+) -> Result<String> {
+    if pepper.len() < PEPPER_MIN_LEN {
+        return Err(Error::InvalidData("fingerprint pepper"));
+    }
+    let mut engine = hmac::HmacEngine::<sha256::Hash>::new(pepper);
+    engine.input(&diagram.to_bytes()?);
+    let hash = hmac::Hmac::from_engine(engine);
+    Ok(style.display(hash.as_byte_array()))
+}
+
+#[cfg(test)]
+mod fingerprint_test {
+    use super::*;
+    use crate::{ComplexDiagram, SimpleDiagram};
+
+    const PEPPER: [u8; 32] = [0x5a; 32];
+
+    #[test]
+    fn test_fingerprint() -> Result<()> {
+        let mut mx = [[None; 7]; 7];
+        mx[1][1] = Some('🍔');
+        mx[5][5] = Some('🌭');
+        let sdm = SimpleDiagram(mx);
+        assert_eq!(
+            sdm.fingerprint(&PEPPER, FingerprintStyle::Emoji)?,
+            "😍☀🐔🏆"
+        );
+        let style = FingerprintStyle::Words(Language::English);
+        assert_eq!(sdm.fingerprint(&PEPPER, style)?, "alpha clarify leisure");
+
+        // one cell changed
+        mx[5][5] = Some('🍟');
+        assert_ne!(
+            SimpleDiagram(mx).fingerprint(&PEPPER, style)?,
+            sdm.fingerprint(&PEPPER, style)?
+        );
+
+        let mut cdm = ComplexDiagram::<7, 7>::default();
+        cdm[1][1] = Some("🍔".to_owned());
+        cdm[5][5] = Some("🌭".to_owned());
+        assert_ne!(
+            cdm.fingerprint(&PEPPER, style)?,
+            sdm.fingerprint(&PEPPER, style)?
+        );
+
+        // other pepper, other fingerprint; short pepper is invalid
+        assert_ne!(
+            sdm.fingerprint(&[0xa5; 32], style)?,
+            sdm.fingerprint(&PEPPER, style)?
+        );
+        assert!(sdm.fingerprint(&PEPPER[..15], style).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_master_fingerprint() {
+        use std::str::FromStr;
+        let master = Xpriv::from_str("xprv9s21ZrQH143K2sW69WDMTge7PMoK1bfeMy3cpNJxfSkqpPsU7DeHZmth8Sw7DVV2AMbC4jR3fKKgDEPJNNvsqhgTfyZwmWj439MWXUW5U5K").unwrap();
+        assert_eq!(FingerprintStyle::Emoji.master(&master), "💊🌙🍄❤");
+        let style = FingerprintStyle::Words(Language::English);
+        assert_eq!(style.master(&master), "prison round dance");
+        assert_eq!(style.display(&[0xff; 5]), "zoo zoo zoo");
+        assert_eq!(FingerprintStyle::Emoji.display(&[]), "😊😊😊😊");
+    }
+}
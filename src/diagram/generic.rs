@@ -1,8 +1,10 @@
-use super::Result;
+use super::fingerprint::{FingerprintStyle, fingerprint};
+use super::{Error, KdfParams, Result};
+use crate::secret::protect;
+use crate::{Language, Mnemonic};
+use bitcoin::Network;
 use bitcoin::bip32::Xpriv;
 
-const DEFAULT_SALT: &[u8] = b"Thanks Satoshi!";
-
 /// Generic Diagram  
 ///   diagram implementation for any matrix
 pub trait GenericDiagram {
@@ -12,60 +14,136 @@ pub trait GenericDiagram {
     /// serialize diagram to binary data
     fn to_bytes(&self) -> Result<Vec<u8>>;
 
+    /// fast fingerprint of diagram data, to confirm diagram typing without key stretching
+    ///
+    /// `pepper` is a secret of the user or device, at least 16 bytes, never a constant.
+    /// fingerprint is short and shared by many diagrams, see `FingerprintStyle`.
+    #[inline]
+    fn fingerprint(&self, pepper: &[u8], style: FingerprintStyle) -> Result<String> {
+        fingerprint(self, pepper, style)
+    }
+
     /// generate warp entropy
     ///
     /// see:
     /// [warp wallet](https://keybase.io/warp),
     /// [go impl](https://github.com/ellisonch/warpwallet)
+    #[inline]
     fn to_entropy(&self, salt: &[u8]) -> Result<[u8; 32]> {
-        let secret = self.to_bytes()?;
-        let mut s1 = {
-            let secret = [&secret[..], &[1u8]].concat();
-            let salt = [DEFAULT_SALT, salt, &[1u8]].concat();
-            let mut output: [u8; 32] = [0; 32];
-            let param = scrypt::Params::new(20, 8, 1, 32)?;
-            scrypt::scrypt(&secret, &salt, &param, &mut output)?;
-            output
-        };
-        let s2 = {
-            let secret = [&secret[..], &[2u8]].concat();
-            let salt = [DEFAULT_SALT, salt, &[2u8]].concat();
-            let mut output: [u8; 32] = [0; 32];
-            argon2::Argon2::default().hash_password_into(&secret, &salt, &mut output)?;
-            output
-        };
-        s1.iter_mut().zip(s2.iter()).for_each(|(a, b)| *a ^= b);
-        Ok(s1)
+        self.to_entropy_with(salt, &KdfParams::V2)
+    }
+
+    /// generate warp entropy with custom key derivation parameters
+    fn to_entropy_with(&self, salt: &[u8], params: &KdfParams) -> Result<[u8; 32]> {
+        params.derive(&protect(self.to_bytes()?), salt)
+    }
+
+    /// generate warp entropy with progress callback
+    ///
+    /// `progress` receives (done, total) work units, returns `false` to cancel.
+    /// scrypt is reported while running, Argon2 or PBKDF2 is reported once when finished,
+    /// cancellation takes effect after them.
+    fn to_entropy_with_progress(
+        &self,
+        salt: &[u8],
+        params: &KdfParams,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<[u8; 32]> {
+        params.derive_with_progress(&protect(self.to_bytes()?), salt, Some(progress))
     }
 
     /// generate extended private key
+    #[inline]
     fn to_master(&self, salt: &[u8]) -> Result<Xpriv> {
-        let seed = self.to_entropy(salt)?;
-        Ok(Xpriv::new_master(crate::NETWORK, &seed)?)
+        self.to_master_with(salt, &KdfParams::V2)
     }
 
     /// generate extended private key
+    #[inline]
     fn to_master_v1(&self, salt: &[u8]) -> Result<Xpriv> {
-        let secret = self.to_bytes()?;
-        let mut s1 = {
-            let secret = [&secret[..], &[1u8]].concat();
-            let salt = [salt, &[1u8]].concat();
-            let mut output: [u8; 32] = [0; 32];
-            let param = scrypt::Params::new(18, 8, 1, 32)?;
-            scrypt::scrypt(&secret, &salt, &param, &mut output)?;
-            output
-        };
-        let s2 = {
-            let secret = [&secret[..], &[2u8]].concat();
-            let salt = [salt, &[2u8]].concat();
-            let mut output: [u8; 32] = [0; 32];
-            pbkdf2::pbkdf2_hmac::<sha2::Sha256>(&secret, &salt, 65536, &mut output);
-            output
-        };
-        s1.iter_mut().zip(s2.iter()).for_each(|(a, b)| *a ^= b);
-
-        let seed = s1;
-        Ok(Xpriv::new_master(crate::NETWORK, &seed)?)
+        self.to_master_with(salt, &KdfParams::V1)
+    }
+
+    /// generate extended private key with custom key derivation parameters
+    fn to_master_with(&self, salt: &[u8], params: &KdfParams) -> Result<Xpriv> {
+        self.to_master_in(salt, params, crate::DEFAULT_NETWORK)
+    }
+
+    /// generate extended private key of network
+    ///
+    /// entropy is same for all networks, only version bytes of master are different.
+    fn to_master_in(&self, salt: &[u8], params: &KdfParams, network: Network) -> Result<Xpriv> {
+        let seed = protect(self.to_entropy_with(salt, params)?);
+        Ok(Xpriv::new_master(network, &seed[..])?)
+    }
+
+    /// generate extended private key with progress callback
+    ///
+    /// `progress` receives (done, total) work units, returns `false` to cancel,
+    /// see `to_entropy_with_progress`.
+    fn to_master_with_progress(
+        &self,
+        salt: &[u8],
+        params: &KdfParams,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<Xpriv> {
+        self.to_master_with_progress_in(salt, params, crate::DEFAULT_NETWORK, progress)
+    }
+
+    /// generate extended private key of network with progress callback
+    fn to_master_with_progress_in(
+        &self,
+        salt: &[u8],
+        params: &KdfParams,
+        network: Network,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<Xpriv> {
+        let seed = protect(self.to_entropy_with_progress(salt, params, progress)?);
+        Ok(Xpriv::new_master(network, &seed[..])?)
+    }
+
+    /// generate BIP39 mnemonic from warp entropy
+    ///
+    /// mnemonic entropy is the first `word_count * 4 / 3` bytes of warp entropy,
+    /// `word_count` must be one of 12, 15, 18, 21, 24.
+    ///
+    /// The BIP39 master of mnemonic (`Mnemonic::to_master`) is a different wallet from `to_master`,
+    /// because BIP39 stretches mnemonic words to seed with PBKDF2.
+    /// 24 words keep the whole warp entropy, so `to_master_in` of any network can be restored
+    /// from them: `Xpriv::new_master(network, &mnemonic.entropy())`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use artimonist::{GenericDiagram, KdfParams, Language, SimpleDiagram, Xpriv};
+    /// # use artimonist::bitcoin::Network;
+    /// # let params = KdfParams { scrypt_log_n: 10, argon2_m_cost: 256, ..KdfParams::V2 };
+    /// let mut mx = [[None; 7]; 7];
+    /// mx[3][3] = Some('🍩');
+    /// let diagram = SimpleDiagram(mx);
+    ///
+    /// let mnemonic = diagram.to_mnemonic_with(b"salt", &params, 24, Language::English)?;
+    /// let master = Xpriv::new_master(Network::Testnet, &mnemonic.entropy())?;
+    /// assert_eq!(master, diagram.to_master_in(b"salt", &params, Network::Testnet)?);
+    /// # Ok::<(), artimonist::Error>(())
+    /// ```
+    #[inline]
+    fn to_mnemonic(&self, salt: &[u8], word_count: usize, language: Language) -> Result<Mnemonic> {
+        self.to_mnemonic_with(salt, &KdfParams::V2, word_count, language)
+    }
+
+    /// generate BIP39 mnemonic with custom key derivation parameters
+    fn to_mnemonic_with(
+        &self,
+        salt: &[u8],
+        params: &KdfParams,
+        word_count: usize,
+        language: Language,
+    ) -> Result<Mnemonic> {
+        if !Mnemonic::VALID_SIZES.contains(&word_count) {
+            return Err(Error::InvalidData("mnemonic size"));
+        }
+        let entropy = protect(self.to_entropy_with(salt, params)?);
+        Ok(Mnemonic::new(&entropy[..word_count * 4 / 3], language)?)
     }
 }
 
@@ -0,0 +1,204 @@
+/*!
+ * # Reference
+ *
+ * [1] - Unicode normalization forms
+ *      <https://unicode.org/reports/tr15/>
+ * [2] - Unicode text segmentation
+ *      <https://unicode.org/reports/tr29/>
+ *
+ * # Descriptions
+ *
+ * [1] - Grapheme Diagram secret data construction
+ *      (diagram version == 0x85)
+ *      |------n segments-----|-n bytes-|-ceil(H*W/8) bytes-|-1 byte-|-1 byte-|-1 byte-|-1 byte-|
+ *      |Grapheme1|Grapheme2|.|N1|N2|...|      Indices      |    H   |    W   |  0x85  |CheckSum|
+ *      |---------->>>--------|--->>>---|-------------------|--------|--------|--------|--------|
+ *      indices are row-major bits of cells, padding with zero bits.
+ *      graphemes are in row-major order.
+ *      all sizes use this construction, including 7 * 7.
+ *
+ * [2] - Grapheme cell
+ *      each cell is exactly one NFC normalized extended grapheme cluster,
+ *      so flags, ZWJ sequences, skin tones and keycaps are kept as a whole,
+ *      and canonically equivalent inputs produce the same secret data.
+**/
+use super::validate::check_cells;
+use super::{
+    DiagramIssue, Error, GenericDiagram, Result, VERSION_GRAPHEME, sized_trailer, split_sized,
+    split_strings, unpack_sized, verify_checksum,
+};
+use crate::secret::ImpSecret;
+use bitcoin::hashes::{Hash, sha256};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Grapheme Diagram
+///
+/// Grapheme diagram contains one grapheme cluster in each of H * W grid cells, 7 * 7 by default.
+/// Cells must be NFC normalized, use `GraphemeDiagram::normalize` to make a cell from any input.
+/// Height and width must be in range 1..=255.
+///
+/// # Examples
+/// ```
+/// # use artimonist::{GenericDiagram, GraphemeDiagram};
+/// let mut gdm = GraphemeDiagram::<7, 7>::default();
+/// gdm[0][0] = GraphemeDiagram::normalize("👨‍👩‍👧");
+/// gdm[3][3] = GraphemeDiagram::normalize("e\u{301}");
+/// assert_eq!(gdm[3][3].as_deref(), Some("é"));
+///
+/// let data = gdm.to_bytes()?;
+/// assert_eq!(GraphemeDiagram::from_bytes(&data)?, gdm);
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+#[derive(Clone, PartialEq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
+pub struct GraphemeDiagram<const H: usize = 7, const W: usize = 7>(pub [[Option<String>; W]; H]);
+ImpSecret!(GraphemeDiagram<const H, const W>, 0);
+
+impl<const H: usize, const W: usize> Default for GraphemeDiagram<H, W> {
+    fn default() -> Self {
+        Self(core::array::from_fn(|_| core::array::from_fn(|_| None)))
+    }
+}
+
+impl<const H: usize, const W: usize> std::ops::Deref for GraphemeDiagram<H, W> {
+    type Target = [[Option<String>; W]; H];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const H: usize, const W: usize> std::ops::DerefMut for GraphemeDiagram<H, W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const H: usize, const W: usize> GenericDiagram for GraphemeDiagram<H, W> {
+    type Item = String;
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut str_list: Vec<&str> = vec![];
+        let mut str_lens: Vec<u8> = vec![];
+        for s in self.iter().flatten().flatten() {
+            if !is_grapheme(s) {
+                return Err(Error::InvalidData("grapheme cell"));
+            }
+            if s.len() > u8::MAX as usize {
+                return Err(Error::StringTooLong(s.to_string()));
+            }
+            str_list.push(s);
+            str_lens.push(s.len() as u8);
+        }
+
+        let cells = self.iter().flatten().map(Option::is_some);
+        let trailer = sized_trailer::<H, W>(cells, VERSION_GRAPHEME)?;
+        let mut secret = [str_list.join("").as_bytes(), &str_lens[..], &trailer].concat();
+        let check = sha256::Hash::hash(&secret).as_byte_array()[0];
+        secret.push(check);
+        Ok(secret)
+    }
+}
+
+impl<const H: usize, const W: usize> GraphemeDiagram<H, W> {
+    /// Deserialize grapheme diagram from secret data of `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let payload = verify_checksum(data)?;
+        let (data, cells) = split_sized::<H, W>(payload, VERSION_GRAPHEME)?;
+        let count = cells.iter().filter(|&&v| v).count();
+        let str_list = split_strings(data, count)?;
+        if !str_list.iter().all(|s| is_grapheme(s)) {
+            return Err(Error::InvalidData("grapheme cell"));
+        }
+        let mx = unpack_sized(&cells, &mut str_list.into_iter())?;
+        Ok(Self(mx))
+    }
+
+    /// Check issues of diagram cells, index = row * W + col
+    pub fn validate(&self) -> Vec<DiagramIssue> {
+        check_cells(self.iter().flatten().map(Option::as_ref))
+    }
+}
+
+impl GraphemeDiagram {
+    /// First grapheme cluster of NFC normalized input, `None` for empty input
+    pub fn normalize(s: &str) -> Option<String> {
+        let s = s.nfc().collect::<String>();
+        s.graphemes(true).next().map(String::from)
+    }
+
+    /// Check if `s` is exactly one NFC normalized grapheme cluster
+    #[inline]
+    pub fn is_grapheme(s: &str) -> bool {
+        is_grapheme(s)
+    }
+}
+
+fn is_grapheme(s: &str) -> bool {
+    let mut it = s.graphemes(true);
+    is_nfc(s) && it.next().is_some() && it.next().is_none()
+}
+
+#[cfg(test)]
+mod grapheme_diagram_test {
+    use super::*;
+    use bitcoin::hex::DisplayHex;
+
+    #[test]
+    fn test_grapheme_diagram() -> Result<()> {
+        const SECRET_HEX: &str = "f09f87baf09f87b8f09f91a8e2808df09f91a9e2808df09f91a7f09f918bf09f8fbd31efb88fe283a3c3a9081208070280208000200080070785c0";
+
+        let mut gdm = GraphemeDiagram::<7, 7>::default();
+        gdm[0][0] = Some("🇺🇸".to_owned());
+        gdm[1][3] = Some("👨‍👩‍👧".to_owned());
+        gdm[2][2] = Some("👋🏽".to_owned());
+        gdm[4][6] = Some("1️⃣".to_owned());
+        gdm[6][6] = Some("é".to_owned());
+        assert_eq!(gdm.to_bytes()?.to_lower_hex_string(), SECRET_HEX);
+        assert_eq!(GraphemeDiagram::from_bytes(&gdm.to_bytes()?)?, gdm);
+
+        let mut gdm5 = GraphemeDiagram::<5, 5>::default();
+        gdm5[4][4] = Some("🍩".to_owned());
+        assert_eq!(
+            GraphemeDiagram::<5, 5>::from_bytes(&gdm5.to_bytes()?)?,
+            gdm5
+        );
+
+        #[cfg(not(feature = "testnet"))]
+        {
+            let master = "xprv9s21ZrQH143K4MgztLuAAKk1qhbs5PqtEgqM6ryrKFpXto63ZrEw358vUW3UheBCK9hUGGCdmZYot7ZfgSbczwV18xNZeZangB3CTwfnbYs";
+            assert_eq!(gdm.to_master("123abc".as_bytes())?.to_string(), master);
+        }
+
+        // not normalized or more than one grapheme
+        for s in ["e\u{301}", "ab", ""] {
+            let mut invalid = gdm.clone();
+            invalid[3][3] = Some(s.to_owned());
+            assert!(matches!(invalid.to_bytes(), Err(Error::InvalidData(_))));
+        }
+
+        // other diagram versions
+        let r = GraphemeDiagram::<5, 5>::from_bytes(&gdm.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+        let cdm = crate::ComplexDiagram::<5, 5>(gdm5.0.clone());
+        let r = GraphemeDiagram::<5, 5>::from_bytes(&cdm.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_grapheme_normalize() {
+        assert_eq!(
+            GraphemeDiagram::normalize("e\u{301}x"),
+            Some("é".to_owned())
+        );
+        assert_eq!(GraphemeDiagram::normalize("🇯🇵🇺🇸"), Some("🇯🇵".to_owned()));
+        assert_eq!(GraphemeDiagram::normalize("👩🏻‍💻!"), Some("👩🏻‍💻".to_owned()));
+        assert_eq!(GraphemeDiagram::normalize(""), None);
+
+        assert!(GraphemeDiagram::is_grapheme("#️⃣"));
+        assert!(GraphemeDiagram::is_grapheme("한"));
+        assert!(!GraphemeDiagram::is_grapheme("\u{1112}\u{1161}\u{11ab}"));
+        assert!(!GraphemeDiagram::is_grapheme("🇯🇵🇺🇸"));
+    }
+}
@@ -0,0 +1,304 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Diagram entropy construction
+ *      s1 = scrypt(secret || 0x1, prefix || salt || 0x1, N, r, p, dkLen=32)
+ *      s2 = argon2(secret || 0x2, prefix || salt || 0x2, m, t, p, dkLen=32)
+ *        or pbkdf2(secret || 0x2, prefix || salt || 0x2, c, dkLen=32, prf=HMAC_SHA256)
+ *      entropy = s1 ⊕ s2
+ *
+ * [2] - Versions
+ *      V1: scrypt(N=2^18, r=8, p=1) ⊕ pbkdf2(c=65536), no prefix
+ *      V2: scrypt(N=2^20, r=8, p=1) ⊕ argon2id(m=19456, t=2, p=1), prefix "Thanks Satoshi!"
+ *
+ * [3] - Parallel
+ *      with `parallel` feature, s1 and s2 are computed in two threads.
+**/
+use super::Result;
+use crate::progress::{Progress, Tracker};
+use crate::secret::protect;
+
+const DEFAULT_SALT: &[u8] = b"Thanks Satoshi!";
+
+/// Argon2 algorithm variant
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Argon2Variant {
+    /// Argon2d, data-dependent memory access
+    Argon2d,
+    /// Argon2i, data-independent memory access
+    Argon2i,
+    /// Argon2id, hybrid of Argon2d and Argon2i
+    #[default]
+    Argon2id,
+}
+
+/// Key derivation parameters of diagram entropy
+///
+/// # Examples
+/// ```
+/// use artimonist::{KdfParams, GenericDiagram, SimpleDiagram};
+///
+/// let params = KdfParams {
+///     scrypt_log_n: 10,
+///     argon2_m_cost: 1024,
+///     ..KdfParams::V2
+/// };
+/// let mut mx = [[None; 7]; 7];
+/// mx[3][3] = Some('🍩');
+/// let master = SimpleDiagram(mx).to_master_with("🚲".as_bytes(), &params)?;
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// scrypt cost parameter, N = 2^scrypt_log_n
+    pub scrypt_log_n: u8,
+    /// scrypt block size
+    pub scrypt_r: u32,
+    /// scrypt parallelization
+    pub scrypt_p: u32,
+    /// Argon2 algorithm variant
+    pub argon2_variant: Argon2Variant,
+    /// Argon2 memory size in KiB
+    pub argon2_m_cost: u32,
+    /// Argon2 number of iterations
+    pub argon2_t_cost: u32,
+    /// Argon2 degree of parallelism
+    pub argon2_p_cost: u32,
+    /// PBKDF2-HMAC-SHA256 rounds, replaces Argon2 when present
+    pub pbkdf2_rounds: Option<u32>,
+    /// Prefix the salt with "Thanks Satoshi!"  
+    /// Argon2 requires at least 8 bytes salt, keep it when salt may be short.
+    pub salt_prefix: bool,
+}
+
+impl KdfParams {
+    /// Parameters of `to_master_v1`
+    pub const V1: KdfParams = KdfParams {
+        scrypt_log_n: 18,
+        scrypt_r: 8,
+        scrypt_p: 1,
+        argon2_variant: Argon2Variant::Argon2id,
+        argon2_m_cost: argon2::Params::DEFAULT_M_COST,
+        argon2_t_cost: argon2::Params::DEFAULT_T_COST,
+        argon2_p_cost: argon2::Params::DEFAULT_P_COST,
+        pbkdf2_rounds: Some(65536),
+        salt_prefix: false,
+    };
+
+    /// Parameters of `to_entropy` and `to_master`
+    pub const V2: KdfParams = KdfParams {
+        scrypt_log_n: 20,
+        scrypt_r: 8,
+        scrypt_p: 1,
+        argon2_variant: Argon2Variant::Argon2id,
+        argon2_m_cost: argon2::Params::DEFAULT_M_COST,
+        argon2_t_cost: argon2::Params::DEFAULT_T_COST,
+        argon2_p_cost: argon2::Params::DEFAULT_P_COST,
+        pbkdf2_rounds: None,
+        salt_prefix: true,
+    };
+
+    /// Memory bytes of one derivation, upper bound of both branches together
+    ///
+    /// scrypt uses 128 * r * (N + p + 2) bytes, Argon2 uses m_cost KiB, PBKDF2 is negligible.
+    /// V2 parameters use about 1 GiB, each batch or recovery worker thread uses this much.
+    pub fn memory_cost(&self) -> u64 {
+        let r = self.scrypt_r as u64;
+        let scrypt = 128 * r * ((1_u64 << self.scrypt_log_n.min(63)) + self.scrypt_p as u64 + 2);
+        let hardening = match self.pbkdf2_rounds {
+            Some(_) => 0,
+            None => self.argon2_m_cost as u64 * 1024,
+        };
+        scrypt.saturating_add(hardening)
+    }
+
+    /// Derive entropy from diagram secret data
+    #[inline]
+    pub(crate) fn derive(&self, secret: &[u8], salt: &[u8]) -> Result<[u8; 32]> {
+        self.derive_with_progress(secret, salt, None)
+    }
+
+    /// Derive entropy from diagram secret data with optional progress callback
+    pub(crate) fn derive_with_progress(
+        &self,
+        secret: &[u8],
+        salt: &[u8],
+        progress: Progress,
+    ) -> Result<[u8; 32]> {
+        let scrypt_params =
+            scrypt::Params::new(self.scrypt_log_n, self.scrypt_r, self.scrypt_p, 32)?;
+        let hardening_units = match self.pbkdf2_rounds {
+            Some(rounds) => rounds as u64,
+            None => Tracker::argon2_units(&self.argon2_params()?),
+        };
+        let total = Tracker::scrypt_units(&scrypt_params) + hardening_units;
+        let mut tracker = Tracker::new(progress, total);
+
+        #[cfg(not(feature = "parallel"))]
+        let (mut s1, s2) = {
+            let s1 = self.scrypt(secret, salt, &scrypt_params, &mut tracker)?;
+            (s1, self.hardening(secret, salt)?)
+        };
+        // hardening branch runs in another thread while scrypt branch reports progress
+        #[cfg(feature = "parallel")]
+        let (mut s1, s2) = std::thread::scope(|scope| -> Result<_> {
+            let hardening = scope.spawn(|| self.hardening(secret, salt));
+            let s1 = self.scrypt(secret, salt, &scrypt_params, &mut tracker);
+            let s2 = hardening
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+            Ok((s1?, s2?))
+        })?;
+        tracker.advance(hardening_units)?;
+
+        let s2 = protect(s2);
+        s1.iter_mut().zip(s2.iter()).for_each(|(a, b)| *a ^= b);
+        Ok(s1)
+    }
+
+    /// scrypt branch
+    fn scrypt(
+        &self,
+        secret: &[u8],
+        salt: &[u8],
+        params: &scrypt::Params,
+        tracker: &mut Tracker,
+    ) -> Result<[u8; 32]> {
+        let secret = protect([secret, &[1u8]].concat());
+        let salt = [self.prefix(), salt, &[1u8]].concat();
+        let mut output: [u8; 32] = [0; 32];
+        tracker.scrypt(&secret, &salt, params, &mut output)?;
+        Ok(output)
+    }
+
+    /// Argon2 or PBKDF2 branch
+    fn hardening(&self, secret: &[u8], salt: &[u8]) -> Result<[u8; 32]> {
+        let secret = protect([secret, &[2u8]].concat());
+        let salt = [self.prefix(), salt, &[2u8]].concat();
+        let mut output: [u8; 32] = [0; 32];
+        match self.pbkdf2_rounds {
+            Some(rounds) => {
+                pbkdf2::pbkdf2_hmac::<sha2::Sha256>(&secret, &salt, rounds, &mut output);
+            }
+            None => {
+                let algorithm = match self.argon2_variant {
+                    Argon2Variant::Argon2d => argon2::Algorithm::Argon2d,
+                    Argon2Variant::Argon2i => argon2::Algorithm::Argon2i,
+                    Argon2Variant::Argon2id => argon2::Algorithm::Argon2id,
+                };
+                argon2::Argon2::new(algorithm, argon2::Version::V0x13, self.argon2_params()?)
+                    .hash_password_into(&secret, &salt, &mut output)?;
+            }
+        }
+        Ok(output)
+    }
+
+    #[inline]
+    fn argon2_params(&self) -> Result<argon2::Params> {
+        Ok(argon2::Params::new(
+            self.argon2_m_cost,
+            self.argon2_t_cost,
+            self.argon2_p_cost,
+            None,
+        )?)
+    }
+
+    #[inline]
+    fn prefix(&self) -> &'static [u8] {
+        if self.salt_prefix { DEFAULT_SALT } else { &[] }
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::V2
+    }
+}
+
+#[cfg(test)]
+mod kdf_test {
+    use super::*;
+    use crate::diagram::Error;
+
+    #[test]
+    fn test_kdf_params() -> Result<()> {
+        const SECRET: &[u8] = b"artimonist";
+        const SALT: &[u8] = b"artimonist salt";
+
+        // scrypt + pbkdf2, same construction as warp wallet
+        let params = KdfParams {
+            scrypt_log_n: 10,
+            pbkdf2_rounds: Some(1024),
+            ..KdfParams::V1
+        };
+        let mut expected = [0u8; 32];
+        scrypt::scrypt(
+            b"artimonist\x01",
+            b"artimonist salt\x01",
+            &scrypt::Params::new(10, 8, 1, 32)?,
+            &mut expected,
+        )?;
+        let mut s2 = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+            b"artimonist\x02",
+            b"artimonist salt\x02",
+            1024,
+            &mut s2,
+        );
+        expected.iter_mut().zip(s2).for_each(|(a, b)| *a ^= b);
+        assert_eq!(params.derive(SECRET, SALT)?, expected);
+
+        // argon2 variants
+        let params = KdfParams {
+            scrypt_log_n: 10,
+            argon2_m_cost: 256,
+            ..KdfParams::V2
+        };
+        let entropy = params.derive(SECRET, SALT)?;
+        let params_i = KdfParams {
+            argon2_variant: Argon2Variant::Argon2i,
+            ..params
+        };
+        assert_ne!(params_i.derive(SECRET, SALT)?, entropy);
+        let params_np = KdfParams {
+            salt_prefix: false,
+            ..params
+        };
+        assert_ne!(params_np.derive(SECRET, SALT)?, entropy);
+
+        // progress and cancellation
+        let mut last = (0, 0);
+        let entropy_p = params.derive_with_progress(
+            SECRET,
+            SALT,
+            Some(&mut |done, total| {
+                assert!(done >= last.0);
+                last = (done, total);
+                true
+            }),
+        )?;
+        assert_eq!(entropy_p, entropy);
+        assert_eq!(
+            last,
+            (2 * 1024 * 8 + 8 * 256 * 2, 2 * 1024 * 8 + 8 * 256 * 2)
+        );
+        let r = params.derive_with_progress(SECRET, SALT, Some(&mut |done, _| done == 0));
+        assert!(matches!(r, Err(Error::Cancelled)));
+
+        // invalid parameters
+        let params = KdfParams {
+            scrypt_log_n: 64,
+            ..KdfParams::V2
+        };
+        let r = params.derive(SECRET, SALT);
+        assert!(matches!(r, Err(Error::EncryptErr(_))));
+
+        // memory of worker threads
+        assert_eq!(
+            KdfParams::V2.memory_cost(),
+            (1 << 30) + 3 * 1024 + 19456 * 1024
+        );
+        assert_eq!(KdfParams::V1.memory_cost(), (1 << 28) + 3 * 1024);
+        Ok(())
+    }
+}
@@ -1,19 +1,50 @@
 mod animate;
+mod animate_complex;
+#[cfg(feature = "parallel")]
+mod batch;
+mod combine;
 mod complex;
 #[allow(clippy::module_inception)]
 mod diagram;
+mod fingerprint;
 mod generic;
+mod grapheme;
+mod kdf;
+mod recovery;
 mod simple;
+mod strength;
+mod text;
+mod validate;
 
 pub use animate::AnimateDiagram;
+pub use animate_complex::AnimateComplexDiagram;
+#[cfg(feature = "parallel")]
+pub use batch::BatchDiagram;
+pub use combine::{CombineDiagram, Combiner, Share};
 pub use complex::ComplexDiagram;
 pub use diagram::Diagram;
+pub use fingerprint::FingerprintStyle;
 pub use generic::GenericDiagram;
+pub use grapheme::GraphemeDiagram;
+pub use kdf::{Argon2Variant, KdfParams};
+pub use recovery::{DiagramRecovery, Recovery, RecoveryTarget};
 pub use simple::SimpleDiagram;
+pub use strength::{DiagramStrength, Strength, StrengthWarning};
+pub use validate::DiagramIssue;
 
 #[cfg(feature = "serde")]
 pub use generic::{Matrix, ToMatrix};
 
+use bitcoin::hashes::{Hash, sha256};
+
+/// Version of sized simple diagram, stored in the byte before checksum.
+/// Legacy diagrams never set the top bit of that byte.
+const VERSION_SIZED_SIMPLE: u8 = 0x80 | 3;
+/// Version of sized complex diagram, stored in the byte before checksum.
+const VERSION_SIZED_COMPLEX: u8 = 0x80 | 4;
+/// Version of grapheme diagram, stored in the byte before checksum.
+const VERSION_GRAPHEME: u8 = 0x80 | 5;
+
 type Result<T = ()> = std::result::Result<T, Error>;
 
 #[derive(thiserror::Error, Debug)]
@@ -24,9 +55,27 @@ pub enum Error {
     /// Bip32 error
     #[error("Derive error: {0}")]
     Bip32Err(#[from] bitcoin::bip32::Error),
+    /// Bip39 error
+    #[error("Mnemonic error: {0}")]
+    Bip39Err(#[from] crate::bip39::Bip39Error),
     /// Argon2 error
     #[error("Encrypt error: {0}")]
     EncryptErr(String),
+    /// Invalid diagram data
+    #[error("invalid data: {0}")]
+    InvalidData(&'static str),
+    /// Invalid diagram version
+    #[error("invalid diagram version")]
+    InvalidVersion,
+    /// Invalid checksum
+    #[error("invalid checksum")]
+    InvalidChecksum,
+    /// Key stretching cancelled
+    #[error("cancelled")]
+    Cancelled,
+    /// Invalid diagram text at line
+    #[error("invalid text at line {0}: {1}")]
+    InvalidText(usize, &'static str),
     /// Decode error
     #[cfg(feature = "serde")]
     #[error("Serde error: {0}")]
@@ -46,7 +95,129 @@ macro_rules! derive_error {
 derive_error!(Error::EncryptErr, scrypt::errors::InvalidParams);
 derive_error!(Error::EncryptErr, scrypt::errors::InvalidOutputLen);
 derive_error!(Error::EncryptErr, argon2::Error);
+
+impl From<crate::progress::StretchError> for Error {
+    fn from(e: crate::progress::StretchError) -> Self {
+        match e {
+            crate::progress::StretchError::Cancelled => Error::Cancelled,
+            crate::progress::StretchError::OutputLen(e) => Error::EncryptErr(e.to_string()),
+        }
+    }
+}
 #[cfg(feature = "serde")]
 derive_error!(Error::SerdeErr, rmp_serde::encode::Error);
 #[cfg(feature = "serde")]
 derive_error!(Error::SerdeErr, rmp_serde::decode::Error);
+
+/// Verify the checksum byte at the end of secret data, returns data without checksum.
+fn verify_checksum(data: &[u8]) -> Result<&[u8]> {
+    let Some((&check, payload)) = data.split_last() else {
+        return Err(Error::InvalidData("empty data"));
+    };
+    if sha256::Hash::hash(payload).as_byte_array()[0] != check {
+        return Err(Error::InvalidChecksum);
+    }
+    Ok(payload)
+}
+
+/// Restore 7 * 7 cells from indices, items are in the order of `to_bytes`.
+fn unpack_cells<T, const H: usize, const W: usize>(
+    indices: &[u8; 7],
+    items: &mut impl Iterator<Item = T>,
+) -> Result<[[Option<T>; W]; H]> {
+    let mut mx = core::array::from_fn(|_| core::array::from_fn(|_| None));
+    (0..W).rev().try_for_each(|col| {
+        (0..H).rev().try_for_each(|row| -> Result {
+            if indices[row] & (1 << (W - 1 - col)) != 0 {
+                let item = items.next().ok_or(Error::InvalidData("items count"))?;
+                mx[row][col] = Some(item);
+            }
+            Ok(())
+        })
+    })?;
+    Ok(mx)
+}
+
+/// Sized diagram trailer: |Indices|H|W|Version|
+///   indices are row-major bits of H * W cells, padding zero bits to bytes.
+fn sized_trailer<const H: usize, const W: usize>(
+    cells: impl Iterator<Item = bool>,
+    version: u8,
+) -> Result<Vec<u8>> {
+    use xbits::FromBits;
+    let (Ok(h @ 1..), Ok(w @ 1..)) = (u8::try_from(H), u8::try_from(W)) else {
+        return Err(Error::InvalidData("diagram size"));
+    };
+    let mut trailer = Vec::from_bits(cells);
+    trailer.extend([h, w, version]);
+    Ok(trailer)
+}
+
+/// Split sized diagram data into items data and row-major cells bits.
+fn split_sized<const H: usize, const W: usize>(
+    payload: &[u8],
+    version: u8,
+) -> Result<(&[u8], Vec<bool>)> {
+    use xbits::XBits;
+    let (rest, &[h, w, ver]) = payload
+        .split_last_chunk::<3>()
+        .ok_or(Error::InvalidData("data too short"))?;
+    if ver != version {
+        return Err(Error::InvalidVersion);
+    }
+    if (h as usize, w as usize) != (H, W) {
+        return Err(Error::InvalidData("diagram size"));
+    }
+
+    let (data, indices) = rest
+        .split_at_checked(rest.len().wrapping_sub((H * W).div_ceil(8)))
+        .ok_or(Error::InvalidData("data too short"))?;
+    let mut cells: Vec<bool> = indices.bits().iter().collect();
+    if cells.drain(H * W..).any(|v| v) {
+        return Err(Error::InvalidData("indices padding"));
+    }
+    Ok((data, cells))
+}
+
+/// Restore H * W cells from row-major cells bits.
+fn unpack_sized<T, const H: usize, const W: usize>(
+    cells: &[bool],
+    items: &mut impl Iterator<Item = T>,
+) -> Result<[[Option<T>; W]; H]> {
+    let mut cells = cells
+        .iter()
+        .map(|&v| match v {
+            true => items
+                .next()
+                .ok_or(Error::InvalidData("items count"))
+                .map(Some),
+            false => Ok(None),
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter();
+    Ok(core::array::from_fn(|_| {
+        core::array::from_fn(|_| cells.next().flatten())
+    }))
+}
+
+/// Split `count` strings from data: |String1|String2|...|N1|N2|...|
+fn split_strings(data: &[u8], count: usize) -> Result<Vec<String>> {
+    let (mut data, str_lens) = data
+        .split_at_checked(data.len().wrapping_sub(count))
+        .ok_or(Error::InvalidData("data too short"))?;
+    if str_lens.iter().map(|&n| n as usize).sum::<usize>() != data.len() {
+        return Err(Error::InvalidData("strings length"));
+    }
+
+    let mut str_list = Vec::with_capacity(count);
+    for &n in str_lens {
+        let (s, rest) = data.split_at(n as usize);
+        let s = std::str::from_utf8(s).map_err(|_| Error::InvalidData("invalid utf8"))?;
+        if s.is_empty() {
+            return Err(Error::InvalidData("empty string"));
+        }
+        str_list.push(s.to_owned());
+        data = rest;
+    }
+    Ok(str_list)
+}
@@ -0,0 +1,391 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Forgotten cell recovery
+ *      candidates = positions * items, one forgotten cell in each candidate.
+ *      positions are searched in the given order, items are searched for each position.
+ *      empty positions means all empty cells of the diagram.
+ *
+ * [2] - Search
+ *      worker threads take the next candidate until matched, cancelled or all done.
+ *      candidates being derived are finished before returning.
+ *      each worker uses `KdfParams::memory_cost` bytes, threads count is chosen by caller.
+ *      progress receives (done, total) candidates count, returns `false` to cancel.
+ *
+ * [3] - Target
+ *      fingerprint: master key fingerprint.
+ *      xpub: master xpub, or account 0 extended public key of BIP44/BIP49/BIP84,
+ *        xpub or any SLIP-132 prefix, compared by key.
+ *      address: first receive address of account 0 in BIP44/BIP49/BIP84.
+**/
+use super::{
+    ComplexDiagram, Error, GenericDiagram, GraphemeDiagram, KdfParams, Result, SimpleDiagram,
+};
+use crate::derive::DeriveInner;
+use crate::slip132::Slip132Pub;
+use crate::{BIP44, BIP49, BIP84};
+use bitcoin::Network;
+use bitcoin::bip32::{Fingerprint, Xpriv, Xpub};
+use bitcoin::key::Secp256k1;
+use std::ops::DerefMut;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// Known target of diagram to recover
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RecoveryTarget {
+    /// Master key fingerprint
+    Fingerprint(Fingerprint),
+    /// Master xpub, or account 0 xpub/ypub/zpub of BIP44/BIP49/BIP84, any SLIP-132 prefix
+    Xpub(String),
+    /// First receive address of account 0 in BIP44/BIP49/BIP84
+    Address(String),
+}
+
+impl RecoveryTarget {
+    /// Check if master key matches the target, accounts and addresses of master network
+    pub fn matches(&self, master: &Xpriv) -> bool {
+        self.matches_in(master, master.network())
+    }
+
+    /// Check if master key matches the target, accounts and addresses of network
+    pub fn matches_in(&self, master: &Xpriv, network: Network) -> bool {
+        match self {
+            Self::Fingerprint(fingerprint) => {
+                master.fingerprint(&Secp256k1::signing_only()) == *fingerprint
+            }
+            Self::Xpub(xpub) => {
+                // ypub, zpub of extfmt are the same keys
+                let Ok(Slip132Pub { xpub, .. }) = xpub.parse() else {
+                    return false;
+                };
+                Xpub::from_priv(&Secp256k1::signing_only(), master) == xpub
+                    || [
+                        master.bip44_derive_account_in(0, network),
+                        master.bip49_derive_account_in(0, network),
+                        master.bip84_derive_account_in(0, network),
+                    ]
+                    .into_iter()
+                    .any(|r| r.is_ok_and(|account| account.xpub == xpub))
+            }
+            Self::Address(address) => [
+                master.bip44_wallet_in(0, 0, false, network),
+                master.bip49_wallet_in(0, 0, false, network),
+                master.bip84_wallet_in(0, 0, false, network),
+            ]
+            .into_iter()
+            .any(|r| r.is_ok_and(|(v, _)| v == *address)),
+        }
+    }
+}
+
+/// Forgotten cell recovery search
+#[derive(Debug, Clone)]
+pub struct Recovery<'a, T> {
+    /// Candidate contents of the forgotten cell
+    pub items: &'a [T],
+    /// Candidate positions (row, col) of the forgotten cell, empty means all empty cells
+    pub positions: &'a [(usize, usize)],
+    /// Known target of the diagram
+    pub target: RecoveryTarget,
+    /// Key derivation parameters
+    pub params: KdfParams,
+    /// Network of candidate masters and target, usually `DEFAULT_NETWORK`
+    pub network: Network,
+    /// Worker threads count, 0 means one thread.
+    /// Each thread uses `params.memory_cost()` bytes, about 1 GiB for `KdfParams::V2`.
+    pub threads: usize,
+}
+
+/// Recover a forgotten cell of diagram
+///
+/// # Examples
+/// ```
+/// use artimonist::{DiagramRecovery, GenericDiagram, KdfParams, Recovery, RecoveryTarget, SimpleDiagram};
+/// use artimonist::DEFAULT_NETWORK;
+/// # use artimonist::bitcoin::key::Secp256k1;
+///
+/// let params = KdfParams {
+///     scrypt_log_n: 10,
+///     argon2_m_cost: 256,
+///     ..KdfParams::V2
+/// };
+/// let mut mx = [[None; 7]; 7];
+/// mx[1][1] = Some('🍔');
+/// mx[3][3] = Some('🍩');
+/// let master = SimpleDiagram(mx).to_master_with(b"salt", &params)?;
+///
+/// // forgot the donut position
+/// mx[3][3] = None;
+/// let recovery = Recovery {
+///     items: &['🍩'],
+///     positions: &[],
+///     target: RecoveryTarget::Fingerprint(master.fingerprint(&Secp256k1::new())),
+///     params,
+///     network: DEFAULT_NETWORK,
+///     threads: 0,
+/// };
+/// let found = SimpleDiagram(mx).recover(b"salt", &recovery, &mut |_, _| true)?;
+/// assert_eq!(found.unwrap()[3][3], Some('🍩'));
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+pub trait DiagramRecovery: Sized {
+    /// cell item type
+    type Item;
+
+    /// Search candidates of the forgotten cell, returns the diagram matching target.
+    ///
+    /// `progress` receives (done, total) candidates count, returns `false` to cancel.
+    fn recover(
+        &self,
+        salt: &[u8],
+        recovery: &Recovery<Self::Item>,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<Option<Self>>;
+}
+
+macro_rules! impl_recovery {
+    ($diagram:ident, $item:ty) => {
+        impl<const H: usize, const W: usize> DiagramRecovery for $diagram<H, W> {
+            type Item = $item;
+
+            fn recover(
+                &self,
+                salt: &[u8],
+                recovery: &Recovery<Self::Item>,
+                progress: &mut dyn FnMut(u64, u64) -> bool,
+            ) -> Result<Option<Self>> {
+                search(self, salt, recovery, progress)
+            }
+        }
+    };
+}
+
+impl_recovery!(SimpleDiagram, char);
+impl_recovery!(ComplexDiagram, String);
+impl_recovery!(GraphemeDiagram, String);
+
+/// Search candidates in worker threads, progress is reported in the calling thread
+fn search<D, T, const H: usize, const W: usize>(
+    diagram: &D,
+    salt: &[u8],
+    recovery: &Recovery<T>,
+    progress: &mut dyn FnMut(u64, u64) -> bool,
+) -> Result<Option<D>>
+where
+    D: GenericDiagram + DerefMut<Target = [[Option<T>; W]; H]> + Clone + Sync,
+    T: Clone + Sync,
+{
+    let positions = match recovery.positions {
+        [] => (0..H * W)
+            .map(|i| (i / W, i % W))
+            .filter(|&(row, col)| diagram[row][col].is_none())
+            .collect(),
+        positions => positions.to_vec(),
+    };
+    if positions.iter().any(|&(row, col)| row >= H || col >= W) {
+        return Err(Error::InvalidData("recovery position"));
+    }
+
+    let items = recovery.items;
+    let count = positions.len() * items.len();
+    let candidate = |i: usize| {
+        let (row, col) = positions[i / items.len()];
+        let mut diagram = diagram.clone();
+        diagram[row][col] = Some(items[i % items.len()].clone());
+        diagram
+    };
+    // 0 means one thread, not more than candidates count
+    let threads = recovery.threads.max(1).min(count);
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (tx, rx) = mpsc::channel::<(usize, Result<bool>)>();
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            let tx = tx.clone();
+            scope.spawn(|| {
+                let tx = tx;
+                while !stop.load(Ordering::Relaxed) {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= count {
+                        break;
+                    }
+                    let matched = candidate(i)
+                        .to_master_in(salt, &recovery.params, recovery.network)
+                        .map(|master| recovery.target.matches_in(&master, recovery.network));
+                    if tx.send((i, matched)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // report progress until matched, cancelled or all done
+        let mut done = 0;
+        let mut result = match progress(0, count as u64) {
+            true => Ok(None),
+            false => Err(Error::Cancelled),
+        };
+        while result.as_ref().is_ok_and(Option::is_none)
+            && let Ok((i, matched)) = rx.recv()
+        {
+            done += 1;
+            result = match matched {
+                Ok(true) => Ok(Some(candidate(i))),
+                Ok(false) if !progress(done, count as u64) => Err(Error::Cancelled),
+                Ok(false) => Ok(None),
+                Err(e) => Err(e),
+            };
+        }
+        stop.store(true, Ordering::Relaxed);
+        result
+    })
+}
+
+#[cfg(test)]
+mod recovery_test {
+    use super::*;
+
+    const PARAMS: KdfParams = KdfParams {
+        scrypt_log_n: 10,
+        argon2_m_cost: 256,
+        ..KdfParams::V2
+    };
+
+    #[test]
+    fn test_simple_recovery() -> Result<()> {
+        let mut mx = [[None; 7]; 7];
+        mx[0][6] = Some('A');
+        mx[2][2] = Some('7');
+        mx[5][4] = Some('😊');
+        let diagram = SimpleDiagram(mx);
+        let master = diagram.to_master_with(b"123abc", &PARAMS)?;
+        let (address, _) = master.bip84_wallet(0, 0, false).unwrap();
+
+        // forgot the char at known position
+        let mut partial = diagram.clone();
+        partial[5][4] = None;
+        let items: Vec<char> = "😊😂🤣❤😍".chars().rev().collect();
+        let recovery = Recovery {
+            items: &items,
+            positions: &[(5, 4)],
+            target: RecoveryTarget::Address(address),
+            params: PARAMS,
+            network: crate::DEFAULT_NETWORK,
+            threads: 2,
+        };
+        let mut last = (0, 0);
+        let found = partial.recover(b"123abc", &recovery, &mut |done, total| {
+            last = (done, total);
+            true
+        })?;
+        assert_eq!(found, Some(diagram.clone()));
+        assert_eq!(last.1, 5);
+
+        // wrong salt is not found
+        let found = partial.recover(b"123", &recovery, &mut |_, _| true)?;
+        assert_eq!(found, None);
+
+        // cancelled
+        let r = partial.recover(b"123", &recovery, &mut |done, _| done < 2);
+        assert!(matches!(r, Err(Error::Cancelled)));
+
+        let r = partial.recover(
+            b"123",
+            &Recovery {
+                positions: &[(7, 0)],
+                ..recovery
+            },
+            &mut |_, _| true,
+        );
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_complex_recovery() -> Result<()> {
+        let mut cdm = ComplexDiagram::<5, 5>::default();
+        cdm[0][0] = Some("ABC".to_owned());
+        cdm[4][1] = Some("测试".to_owned());
+        let master = cdm.to_master_with(&[], &PARAMS)?;
+        let (xpub, _) = master.bip49_account(0).unwrap();
+
+        // forgot the position of known string
+        let mut partial = cdm.clone();
+        partial[4][1] = None;
+        let recovery = Recovery {
+            items: &["测试".to_owned()],
+            positions: &[],
+            target: RecoveryTarget::Xpub(xpub),
+            params: PARAMS,
+            network: crate::DEFAULT_NETWORK,
+            threads: 0,
+        };
+        let found = partial.recover(&[], &recovery, &mut |_, _| true)?;
+        assert_eq!(found, Some(cdm));
+        Ok(())
+    }
+
+    #[test]
+    fn test_network_recovery() -> Result<()> {
+        let mut mx = [[None; 7]; 7];
+        mx[1][1] = Some('🍔');
+        mx[3][3] = Some('🍩');
+        let diagram = SimpleDiagram(mx);
+        let master = diagram.to_master_in(b"salt", &PARAMS, Network::Regtest)?;
+        let (address, _) = master
+            .bip84_wallet_in(0, 0, false, Network::Regtest)
+            .unwrap();
+        assert!(address.starts_with("bcrt1"));
+
+        let mut partial = diagram.clone();
+        partial[3][3] = None;
+        let recovery = Recovery {
+            items: &['🍟', '🍩'],
+            positions: &[(3, 3)],
+            target: RecoveryTarget::Address(address),
+            params: PARAMS,
+            network: Network::Regtest,
+            threads: 0,
+        };
+        let found = partial.recover(b"salt", &recovery, &mut |_, _| true)?;
+        assert_eq!(found, Some(diagram));
+
+        // testnet addresses of same keys are different
+        let recovery = Recovery {
+            network: Network::Testnet,
+            ..recovery
+        };
+        assert_eq!(partial.recover(b"salt", &recovery, &mut |_, _| true)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_xpub_target() -> Result<()> {
+        use crate::slip132::Slip132;
+        let mut mx = [[None; 7]; 7];
+        mx[2][5] = Some('🍔');
+        let master = SimpleDiagram(mx).to_master_with(b"salt", &PARAMS)?;
+        let account = master.bip84_derive_account(0).unwrap().xpub;
+
+        // same key of xpub and zpub, formatted with or without extfmt
+        let (formatted, _) = master.bip84_account(0).unwrap();
+        let targets = [
+            account.to_string(),
+            Slip132Pub::new(account, Slip132::Z).to_string(),
+            formatted,
+            Xpub::from_priv(&Secp256k1::signing_only(), &master).to_string(),
+        ];
+        for target in targets {
+            assert!(RecoveryTarget::Xpub(target).matches(&master));
+        }
+
+        let other = master.bip84_derive_account(1).unwrap().xpub;
+        assert!(!RecoveryTarget::Xpub(other.to_string()).matches(&master));
+        assert!(!RecoveryTarget::Xpub("xpub".to_owned()).matches(&master));
+        Ok(())
+    }
+}
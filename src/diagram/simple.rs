@@ -22,15 +22,28 @@
  *      0b0xxx_xxxx
  *      0b0xxx_xxxx
  *      x bits indices string position in diagram.
+ *
+ * [3] - Sized Simple Diagram secret data construction
+ *      (H * W != 7 * 7, diagram version == 0x83)
+ *      |--utf8 chars---|-ceil(H*W/8) bytes-|-1 byte-|-1 byte-|-1 byte-|-1 byte-|
+ *      |Char1|Char2|...|      Indices      |    H   |    W   |  0x83  |CheckSum|
+ *      |---------------|-------------------|--------|--------|--------|--------|
+ *      indices are row-major bits of cells, padding with zero bits.
+ *      chars are in row-major order.
 **/
-use super::{GenericDiagram, Result};
-use crate::macros::ImpDeref;
+use super::validate::check_cells;
+use super::{
+    DiagramIssue, Error, GenericDiagram, Result, VERSION_SIZED_SIMPLE, sized_trailer, split_sized,
+    unpack_cells, unpack_sized, verify_checksum,
+};
+use crate::secret::ImpSecret;
 use bitcoin::hashes::{Hash, sha256};
 
 /// Simple Diagram
 ///
-/// `Simple Diagram' contains arbitrary characters in 7 * 7 grid cells.
+/// `Simple Diagram' contains arbitrary characters in H * W grid cells, 7 * 7 by default.
 /// All Unicode characters are supported.
+/// Height and width must be in range 1..=255.
 ///
 /// # Examples
 /// ```
@@ -46,15 +59,55 @@ use bitcoin::hashes::{Hash, sha256};
 /// # Ok::<(), artimonist::Error>(())
 /// ```
 ///
-#[derive(Debug, Default, Clone, PartialEq)]
-pub struct SimpleDiagram(pub [[Option<char>; 7]; 7]);
-ImpDeref!(SimpleDiagram, [[Option<char>; 7]; 7]);
+/// Diagrams of other sizes:
+/// ```
+/// # use artimonist::{GenericDiagram, SimpleDiagram};
+/// let mut diagram = SimpleDiagram::<5, 5>::default();
+/// diagram[4][0] = Some('🌈');
+///
+/// let data = diagram.to_bytes()?;
+/// assert_eq!(SimpleDiagram::<5, 5>::from_bytes(&data)?, diagram);
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+#[derive(Clone, PartialEq)]
+#[cfg_attr(not(feature = "zeroize"), derive(Debug))]
+pub struct SimpleDiagram<const H: usize = 7, const W: usize = 7>(pub [[Option<char>; W]; H]);
+ImpSecret!(SimpleDiagram<const H, const W>, 0);
+
+impl<const H: usize, const W: usize> Default for SimpleDiagram<H, W> {
+    fn default() -> Self {
+        Self([[None; W]; H])
+    }
+}
+
+impl<const H: usize, const W: usize> std::ops::Deref for SimpleDiagram<H, W> {
+    type Target = [[Option<char>; W]; H];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const H: usize, const W: usize> std::ops::DerefMut for SimpleDiagram<H, W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
 
-impl GenericDiagram for SimpleDiagram {
+impl<const H: usize, const W: usize> GenericDiagram for SimpleDiagram<H, W> {
     type Item = char;
 
     /// Compatible with previous versions
     fn to_bytes(&self) -> Result<Vec<u8>> {
+        if (H, W) != (7, 7) {
+            let str = self.iter().flatten().flatten().collect::<String>();
+            let cells = self.iter().flatten().map(Option::is_some);
+            let trailer = sized_trailer::<H, W>(cells, VERSION_SIZED_SIMPLE)?;
+            let mut secret = [str.as_bytes(), &trailer].concat();
+            let check = sha256::Hash::hash(&secret).as_byte_array()[0];
+            secret.push(check);
+            return Ok(secret);
+        }
+
         let mut chars = Vec::with_capacity(7 * 7);
         let mut indices = [0; 7];
         (0..7).rev().for_each(|col| {
@@ -75,6 +128,43 @@ impl GenericDiagram for SimpleDiagram {
     }
 }
 
+impl<const H: usize, const W: usize> SimpleDiagram<H, W> {
+    /// Deserialize simple diagram from secret data of `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let payload = verify_checksum(data)?;
+        if (H, W) != (7, 7) {
+            let (chars, cells) = split_sized::<H, W>(payload, VERSION_SIZED_SIMPLE)?;
+            let str = std::str::from_utf8(chars).map_err(|_| Error::InvalidData("invalid utf8"))?;
+            let mut chars = str.chars();
+            let mx = unpack_sized(&cells, &mut chars)?;
+            if chars.next().is_some() {
+                return Err(Error::InvalidData("items count"));
+            }
+            return Ok(Self(mx));
+        }
+
+        let (chars, indices) = payload
+            .split_last_chunk::<7>()
+            .ok_or(Error::InvalidData("data too short"))?;
+        if indices.iter().any(|v| v & 0x80 != 0) {
+            return Err(Error::InvalidVersion);
+        }
+
+        let str = std::str::from_utf8(chars).map_err(|_| Error::InvalidData("invalid utf8"))?;
+        let mut chars = str.chars();
+        let mx = unpack_cells(indices, &mut chars)?;
+        if chars.next().is_some() {
+            return Err(Error::InvalidData("items count"));
+        }
+        Ok(Self(mx))
+    }
+
+    /// Check issues of diagram cells, index = row * W + col
+    pub fn validate(&self) -> Vec<DiagramIssue> {
+        check_cells(self.iter().flatten().map(|v| v.map(String::from)))
+    }
+}
+
 impl SimpleDiagram {
     /// create simple diagram
     #[deprecated(since = "1.7.2", note = "Use `Diagram` instead")]
@@ -163,12 +253,110 @@ mod simple_diagram_test {
         let sdm = SimpleDiagram::from_values(&items, CHARS_INDICES);
         assert_eq!(sdm.to_bytes()?.to_lower_hex_string(), SECRET_HEX);
         assert_eq!(sdm[6][0], Some('😊'));
+        assert_eq!(SimpleDiagram::from_bytes(&sdm.to_bytes()?)?, sdm);
 
         let entropy = sdm.to_entropy(Default::default())?;
         assert_eq!(entropy.to_lower_hex_string(), WARP_ENTROPY);
         let entropy = sdm.to_entropy(SALT_STR.as_bytes())?;
         assert_eq!(entropy.to_lower_hex_string(), SALT_ENTROPY);
 
+        let mnemonic = sdm.to_mnemonic(SALT_STR.as_bytes(), 12, crate::Language::English)?;
+        assert_eq!(
+            mnemonic.to_string(),
+            "veteran priority crash because lizard rail indoor icon seminar mind silly equip"
+        );
+        assert_eq!(mnemonic.entropy().to_lower_hex_string(), SALT_ENTROPY[..32]);
+        let r = sdm.to_mnemonic(SALT_STR.as_bytes(), 13, crate::Language::English);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simple_from_bytes() -> Result<()> {
+        use bitcoin::hex::FromHex;
+        const SECRET_HEX: &str = "f09f988a412ae78e8b26012800001000012d";
+        const CHARS_STR: &str = "A&*王😊";
+        const CHARS_INDICES: &[(usize, usize)] = &[(0, 6), (1, 1), (1, 3), (4, 2), (6, 6)];
+
+        let data = Vec::from_hex(SECRET_HEX).unwrap();
+        let items: Vec<char> = CHARS_STR.chars().collect();
+        let sdm = SimpleDiagram::from_values(&items, CHARS_INDICES);
+        assert_eq!(SimpleDiagram::from_bytes(&data)?, sdm);
+
+        let empty = SimpleDiagram::new();
+        assert_eq!(SimpleDiagram::from_bytes(&empty.to_bytes()?)?, empty);
+
+        // invalid checksum
+        let mut invalid = data.clone();
+        *invalid.last_mut().unwrap() ^= 1;
+        let r: Result<SimpleDiagram> = SimpleDiagram::from_bytes(&invalid);
+        assert!(matches!(r, Err(Error::InvalidChecksum)));
+
+        // complex diagram version
+        let cdm = crate::ComplexDiagram::from_values(&["A"], &[(0, 0)]);
+        let r: Result<SimpleDiagram> = SimpleDiagram::from_bytes(&cdm.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+
+        // chars count mismatch
+        let mut invalid = data[4..data.len() - 1].to_vec();
+        invalid.push(sha256::Hash::hash(&invalid).as_byte_array()[0]);
+        let r: Result<SimpleDiagram> = SimpleDiagram::from_bytes(&invalid);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+
+        let r: Result<SimpleDiagram> = SimpleDiagram::from_bytes(&[]);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sized_simple_diagram() -> Result<()> {
+        const SECRET_5X5: &str = "41e78e8bf09f988a8004008005058391";
+        const SECRET_9X9: &str = "f09f8da926e282ac008000000080000000800009098391";
+        const SECRET_12X12: &str = "f09f90b62ae298950000020000000000002000000000000000010c0c8365";
+
+        let mut sdm5 = SimpleDiagram::<5, 5>::default();
+        sdm5[0][0] = Some('A');
+        sdm5[2][3] = Some('王');
+        sdm5[4][4] = Some('😊');
+        assert_eq!(sdm5.to_bytes()?.to_lower_hex_string(), SECRET_5X5);
+        assert_eq!(SimpleDiagram::<5, 5>::from_bytes(&sdm5.to_bytes()?)?, sdm5);
+
+        let mut sdm9 = SimpleDiagram::<9, 9>::default();
+        sdm9[0][8] = Some('🍩');
+        sdm9[4][4] = Some('&');
+        sdm9[8][0] = Some('€');
+        assert_eq!(sdm9.to_bytes()?.to_lower_hex_string(), SECRET_9X9);
+        assert_eq!(SimpleDiagram::<9, 9>::from_bytes(&sdm9.to_bytes()?)?, sdm9);
+
+        let mut sdm12 = SimpleDiagram::<12, 12>::default();
+        sdm12[1][10] = Some('🐶');
+        sdm12[6][2] = Some('*');
+        sdm12[11][11] = Some('☕');
+        assert_eq!(sdm12.to_bytes()?.to_lower_hex_string(), SECRET_12X12);
+        assert_eq!(
+            SimpleDiagram::<12, 12>::from_bytes(&sdm12.to_bytes()?)?,
+            sdm12
+        );
+
+        #[cfg(not(feature = "testnet"))]
+        {
+            let master = "xprv9s21ZrQH143K4Qm9cRi3Pc9QPGrCnrJ9Fyyur4H2HCqGJctNEq4ZmUyD5wjA7CU6hyJ5YZxKSHAb1nRy2H4SE3zpA2g6DZuMKizYGjn6Yac";
+            assert_eq!(sdm5.to_master("123abc".as_bytes())?.to_string(), master);
+        }
+
+        // size mismatch
+        let r = SimpleDiagram::<9, 9>::from_bytes(&sdm5.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidData(_))));
+
+        // legacy and sized versions are not interchangeable
+        let r = SimpleDiagram::<5, 5>::from_bytes(&SimpleDiagram::new().to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        let r: Result<SimpleDiagram> = SimpleDiagram::from_bytes(&sdm5.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
+        let cdm5 = crate::ComplexDiagram::<5, 5>::default();
+        let r = SimpleDiagram::<5, 5>::from_bytes(&cdm5.to_bytes()?);
+        assert!(matches!(r, Err(Error::InvalidVersion)));
         Ok(())
     }
 }
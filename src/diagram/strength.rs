@@ -0,0 +1,399 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Diagram strength estimation
+ *      position bits = log2(C(H * W, k)), k is filled cells count
+ *      content bits = sum(log2(alphabet size of each char)), distinct contents only
+ *        a dictionary word counts as one of 2048 words
+ *      total bits = position bits + content bits
+ *      animate diagrams sum up bits of all frames.
+ *      generic matrices are estimated by display strings of items, as complex diagram cells.
+ *
+ * [2] - Alphabet size estimation by Unicode block
+ *      ASCII digits: 10, ASCII letters: 52, other printable ASCII: 95
+ *      Latin-1 supplement & Latin extended: 190
+ *      Hiragana & Katakana: 170
+ *      CJK ideographs: 3500 (commonly used)
+ *      Hangul syllables: 2350 (commonly used)
+ *      Emoji & symbols: 1400
+ *      others: 1000
+**/
+use super::{
+    AnimateComplexDiagram, AnimateDiagram, ComplexDiagram, GraphemeDiagram, SimpleDiagram,
+};
+use crate::Language;
+use std::collections::HashSet;
+use unicode_normalization::UnicodeNormalization;
+
+/// Diagram strength report
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strength {
+    /// Filled cells count
+    pub filled_cells: usize,
+    /// All cells count
+    pub total_cells: usize,
+    /// Entropy bits of filled cells positions
+    pub position_bits: f64,
+    /// Estimated entropy bits of cells contents
+    pub content_bits: f64,
+    /// Estimated total entropy bits
+    pub total_bits: f64,
+    /// Weak patterns found in diagram
+    pub warnings: Vec<StrengthWarning>,
+}
+
+impl Strength {
+    /// Diagrams with less total bits are considered weak
+    pub const WEAK_BITS: f64 = 80.0;
+
+    /// Check if total bits estimate is below `WEAK_BITS`
+    #[inline]
+    pub fn is_weak(&self) -> bool {
+        self.total_bits < Self::WEAK_BITS
+    }
+}
+
+/// Weak pattern of diagram
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StrengthWarning {
+    /// Filled cells are on a diagonal
+    Diagonal,
+    /// Filled cells are in a single row or column
+    Line,
+    /// Filled cells layout is mirror or rotation symmetric
+    Symmetric,
+    /// Same content is in several cells
+    Repeated(String),
+    /// Cell content is a dictionary word
+    DictionaryWord(String),
+}
+
+/// Diagram strength estimator
+/// Implemented for all diagram types, and generic matrices of displayable items.
+///
+/// # Examples
+/// ```
+/// # use artimonist::{DiagramStrength, SimpleDiagram, StrengthWarning};
+/// let mut mx = [[None; 7]; 7];
+/// (0..7).for_each(|i| mx[i][i] = Some('A'));
+///
+/// let strength = SimpleDiagram(mx).strength();
+/// assert!(strength.is_weak());
+/// assert!(strength.warnings.contains(&StrengthWarning::Diagonal));
+/// ```
+pub trait DiagramStrength {
+    /// Estimate diagram strength
+    fn strength(&self) -> Strength;
+}
+
+impl<const H: usize, const W: usize> DiagramStrength for SimpleDiagram<H, W> {
+    fn strength(&self) -> Strength {
+        let mut estimator = Estimator::default();
+        estimator.frame(
+            H,
+            W,
+            self.iter().map(|row| row.map(|v| v.map(String::from))),
+            false,
+        );
+        estimator.finish()
+    }
+}
+
+impl<const H: usize, const W: usize> DiagramStrength for ComplexDiagram<H, W> {
+    fn strength(&self) -> Strength {
+        let mut estimator = Estimator::default();
+        let rows = self
+            .iter()
+            .map(|row| row.clone().map(|v| v.filter(|s| !s.is_empty())));
+        estimator.frame(H, W, rows, true);
+        estimator.finish()
+    }
+}
+
+impl<const H: usize, const W: usize> DiagramStrength for GraphemeDiagram<H, W> {
+    fn strength(&self) -> Strength {
+        let mut estimator = Estimator::default();
+        estimator.frame(H, W, self.iter().cloned(), false);
+        estimator.finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: ToString, const H: usize, const W: usize> DiagramStrength for super::Matrix<T, H, W> {
+    fn strength(&self) -> Strength {
+        let mut estimator = Estimator::default();
+        let rows = self.iter().map(|row| {
+            row.each_ref()
+                .map(|v| v.as_ref().map(T::to_string).filter(|s| !s.is_empty()))
+        });
+        estimator.frame(H, W, rows, true);
+        estimator.finish()
+    }
+}
+
+impl DiagramStrength for AnimateDiagram {
+    fn strength(&self) -> Strength {
+        let mut estimator = Estimator::default();
+        self.0.iter().for_each(|mx| {
+            estimator.frame(
+                7,
+                7,
+                mx.iter().map(|row| row.map(|v| v.map(String::from))),
+                false,
+            );
+        });
+        estimator.finish()
+    }
+}
+
+impl DiagramStrength for AnimateComplexDiagram {
+    fn strength(&self) -> Strength {
+        let mut estimator = Estimator::default();
+        self.0.iter().for_each(|mx| {
+            let rows = mx
+                .iter()
+                .map(|row| row.clone().map(|v| v.filter(|s| !s.is_empty())));
+            estimator.frame(7, 7, rows, true);
+        });
+        estimator.finish()
+    }
+}
+
+#[derive(Default)]
+struct Estimator {
+    filled_cells: usize,
+    total_cells: usize,
+    position_bits: f64,
+    content_bits: f64,
+    contents: HashSet<String>,
+    warnings: Vec<StrengthWarning>,
+}
+
+impl Estimator {
+    /// Estimate one diagram frame of h * w cells
+    fn frame<const W: usize>(
+        &mut self,
+        h: usize,
+        w: usize,
+        rows: impl Iterator<Item = [Option<String>; W]>,
+        dictionary: bool,
+    ) {
+        let mut positions = Vec::new();
+        rows.enumerate().for_each(|(row, cells)| {
+            cells.into_iter().enumerate().for_each(|(col, cell)| {
+                if let Some(s) = cell {
+                    positions.push((row, col));
+                    self.content(s, dictionary);
+                }
+            })
+        });
+
+        self.filled_cells += positions.len();
+        self.total_cells += h * w;
+        self.position_bits += log2_binomial(h * w, positions.len());
+        if positions.len() < 2 {
+            return;
+        }
+
+        let diagonal = positions.iter().all(|&(r, c)| r == c)
+            || positions.iter().all(|&(r, c)| r + c == w - 1);
+        if diagonal {
+            self.warn(StrengthWarning::Diagonal);
+        }
+        let line = positions.iter().all(|&(r, _)| r == positions[0].0)
+            || positions.iter().all(|&(_, c)| c == positions[0].1);
+        if line {
+            self.warn(StrengthWarning::Line);
+        }
+
+        let layout: HashSet<(usize, usize)> = positions.iter().copied().collect();
+        // horizontal mirror, vertical mirror, 180° rotation, transpose of square
+        let symmetric = (0..if h == w { 4 } else { 3 }).any(|kind| {
+            positions.iter().all(|&(r, c)| {
+                let p = match kind {
+                    0 => (r, w - 1 - c),
+                    1 => (h - 1 - r, c),
+                    2 => (h - 1 - r, w - 1 - c),
+                    _ => (c, r),
+                };
+                layout.contains(&p)
+            })
+        });
+        if symmetric {
+            self.warn(StrengthWarning::Symmetric);
+        }
+    }
+
+    /// Estimate cell content, repeated contents add no bits
+    fn content(&mut self, s: String, dictionary: bool) {
+        if self.contents.contains(&s) {
+            self.warn(StrengthWarning::Repeated(s));
+            return;
+        }
+
+        let word = s.trim().to_lowercase().nfkd().collect::<String>();
+        if dictionary && !Language::detect(&word).is_empty() {
+            self.content_bits += 2048_f64.log2();
+            self.warn(StrengthWarning::DictionaryWord(s.clone()));
+        } else {
+            self.content_bits += s.chars().map(|ch| alphabet_size(ch).log2()).sum::<f64>();
+        }
+        self.contents.insert(s);
+    }
+
+    fn warn(&mut self, warning: StrengthWarning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    fn finish(self) -> Strength {
+        Strength {
+            filled_cells: self.filled_cells,
+            total_cells: self.total_cells,
+            position_bits: self.position_bits,
+            content_bits: self.content_bits,
+            total_bits: self.position_bits + self.content_bits,
+            warnings: self.warnings,
+        }
+    }
+}
+
+/// Estimated alphabet size of character's Unicode block
+fn alphabet_size(ch: char) -> f64 {
+    match ch as u32 {
+        0x30..=0x39 => 10.0,
+        0x41..=0x5a | 0x61..=0x7a => 52.0,
+        0x20..=0x7e => 95.0,
+        0xa0..=0x24f => 190.0,
+        0x3040..=0x30ff => 170.0,
+        0x3400..=0x4dbf | 0x4e00..=0x9fff => 3500.0,
+        0xac00..=0xd7a3 => 2350.0,
+        0x2600..=0x27bf | 0x1f000..=0x1faff => 1400.0,
+        _ => 1000.0,
+    }
+}
+
+/// log2 of binomial coefficient C(n, k)
+fn log2_binomial(n: usize, k: usize) -> f64 {
+    (0..k.min(n))
+        .map(|i| ((n - i) as f64 / (i + 1) as f64).log2())
+        .sum()
+}
+
+#[cfg(test)]
+mod strength_test {
+    use super::*;
+
+    #[test]
+    fn test_simple_strength() {
+        let strength = SimpleDiagram::<7, 7>::default().strength();
+        assert_eq!(strength.filled_cells, 0);
+        assert_eq!(strength.total_bits, 0.0);
+        assert!(strength.is_weak());
+
+        let mut mx = [[None; 7]; 7];
+        mx[0][1] = Some('A');
+        mx[2][5] = Some('王');
+        mx[3][3] = Some('😊');
+        mx[6][2] = Some('7');
+        let strength = SimpleDiagram(mx).strength();
+        assert_eq!(strength.filled_cells, 4);
+        assert_eq!(strength.total_cells, 49);
+        let position_bits = (49.0 * 48.0 * 47.0 * 46.0 / 24.0_f64).log2();
+        assert!((strength.position_bits - position_bits).abs() < 1e-9);
+        let content_bits = (52.0 * 3500.0 * 1400.0 * 10.0_f64).log2();
+        assert!((strength.content_bits - content_bits).abs() < 1e-9);
+        assert!(strength.warnings.is_empty());
+
+        // weak patterns
+        let mut mx = [[None; 7]; 7];
+        mx[0][6] = Some('A');
+        mx[3][3] = Some('A');
+        mx[6][0] = Some('B');
+        let strength = SimpleDiagram(mx).strength();
+        assert_eq!(
+            strength.warnings,
+            [
+                StrengthWarning::Repeated("A".to_owned()),
+                StrengthWarning::Diagonal,
+                StrengthWarning::Symmetric,
+            ]
+        );
+        assert!((strength.content_bits - (52.0 * 52.0_f64).log2()).abs() < 1e-9);
+
+        let mut mx = [[None; 5]; 5];
+        mx[1][0] = Some('x');
+        mx[1][4] = Some('y');
+        let strength = SimpleDiagram(mx).strength();
+        assert_eq!(strength.total_cells, 25);
+        assert_eq!(
+            strength.warnings,
+            [StrengthWarning::Line, StrengthWarning::Symmetric]
+        );
+    }
+
+    #[test]
+    fn test_complex_strength() {
+        let mut cdm = ComplexDiagram::<7, 7>::default();
+        cdm[0][0] = Some("Abandon".to_owned());
+        cdm[2][4] = Some("混A1".to_owned());
+        cdm[5][1] = Some("测试".to_owned());
+        cdm[6][6] = Some(String::new());
+        let strength = cdm.strength();
+        assert_eq!(strength.filled_cells, 3);
+        assert_eq!(
+            strength.warnings,
+            [StrengthWarning::DictionaryWord("Abandon".to_owned())]
+        );
+        let content_bits = 11.0 + (3500.0 * 52.0 * 10.0 * 3500.0 * 3500.0_f64).log2();
+        assert!((strength.content_bits - content_bits).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_animate_strength() {
+        let mut frame = [[None; 7]; 7];
+        frame[1][2] = Some('🍩');
+        let mut frame2 = [[None; 7]; 7];
+        frame2[4][3] = Some('🍩');
+        frame2[6][5] = Some('&');
+        let strength = AnimateDiagram(vec![frame, frame2]).strength();
+        assert_eq!(strength.filled_cells, 3);
+        assert_eq!(strength.total_cells, 98);
+        let position_bits = 49_f64.log2() + (49.0 * 48.0 / 2.0_f64).log2();
+        assert!((strength.position_bits - position_bits).abs() < 1e-9);
+        assert_eq!(
+            strength.warnings,
+            [StrengthWarning::Repeated("🍩".to_owned())]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_generic_strength() {
+        use crate::Matrix;
+        let mut cdm = ComplexDiagram::<7, 7>::default();
+        cdm[0][0] = Some("Abandon".to_owned());
+        cdm[3][5] = Some("混A1".to_owned());
+        let mut mx: Matrix<&str> = Default::default();
+        mx[0][0] = Some("Abandon");
+        mx[3][5] = Some("混A1");
+        assert_eq!(mx.strength(), cdm.strength());
+
+        let mut mx: Matrix<u32, 5, 5> = Default::default();
+        mx[1][1] = Some(42);
+        mx[3][3] = Some(42);
+        let strength = mx.strength();
+        assert_eq!((strength.filled_cells, strength.total_cells), (2, 25));
+        assert!((strength.content_bits - 2.0 * 10_f64.log2()).abs() < 1e-9);
+        assert_eq!(
+            strength.warnings,
+            [
+                StrengthWarning::Repeated("42".to_owned()),
+                StrengthWarning::Diagonal,
+                StrengthWarning::Symmetric,
+            ]
+        );
+    }
+}
@@ -0,0 +1,514 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Diagram text format (markdown table)
+ *      |  |  |  |  |  |  |  |      row 0 as table header
+ *      |--|--|--|--|--|--|--|      separator, W cells of '-' or ':'
+ *      |  |🍔|  |  |  |🍟|  |      row 1
+ *      |  |  |  |  |  |  |  |      row 2
+ *      ...                         until row H - 1
+ *      each row contains W cells.
+ *      spaces around cell content are ignored, empty cell means no content.
+ *      simple diagram cell contains exactly one character.
+ *      complex diagram cell contains at most 50 characters.
+ *      grapheme diagram cell contains exactly one grapheme, NFC normalized when parsing.
+ *      animate diagram frames are separated by blank lines.
+ *      animate complex diagram cell contains at most 50 characters.
+ *
+ * [2] - Cell content escaping
+ *      \|  =>  '|'
+ *      \\  =>  '\'
+ *      \s  =>  ' '
+ *      \t  =>  tab
+ *      \n  =>  line feed
+ *      \r  =>  carriage return
+ *      \u{hex}  =>  unicode scalar value, other control characters are written so.
+ *      unescaped control characters are not allowed.
+**/
+use super::{
+    AnimateComplexDiagram, AnimateDiagram, ComplexDiagram, Error, GraphemeDiagram, Result,
+    SimpleDiagram,
+};
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+impl<const H: usize, const W: usize> Display for SimpleDiagram<H, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = self.iter().map(|row| row.map(|v| v.map(String::from)));
+        write_table(f, rows)
+    }
+}
+
+impl<const H: usize, const W: usize> FromStr for SimpleDiagram<H, W> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [table] = &tables(s)[..] else {
+            return Err(Error::InvalidText(0, "expect one table"));
+        };
+        Ok(Self(parse_chars(table)?))
+    }
+}
+
+impl<const H: usize, const W: usize> Display for ComplexDiagram<H, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_table(f, self.iter().cloned())
+    }
+}
+
+impl<const H: usize, const W: usize> FromStr for ComplexDiagram<H, W> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [table] = &tables(s)[..] else {
+            return Err(Error::InvalidText(0, "expect one table"));
+        };
+        Ok(Self(parse_strings(table, Self::CELL_CHARS_LIMIT)?))
+    }
+}
+
+impl<const H: usize, const W: usize> Display for GraphemeDiagram<H, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_table(f, self.iter().cloned())
+    }
+}
+
+impl<const H: usize, const W: usize> FromStr for GraphemeDiagram<H, W> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        use unicode_normalization::UnicodeNormalization;
+        let [table] = &tables(s)[..] else {
+            return Err(Error::InvalidText(0, "expect one table"));
+        };
+        let mut mx = parse_table::<H, W>(table)?;
+        for (n, row) in mx.iter_mut().enumerate() {
+            for cell in row.iter_mut().flatten() {
+                *cell = cell.nfc().collect();
+                if !GraphemeDiagram::is_grapheme(cell) {
+                    return Err(Error::InvalidText(line_of(table, n), "expect one grapheme"));
+                }
+            }
+        }
+        Ok(Self(mx))
+    }
+}
+
+impl Display for AnimateDiagram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, mx) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_char('\n')?;
+            }
+            write_table(f, mx.iter().map(|row| row.map(|v| v.map(String::from))))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AnimateDiagram {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let tables = tables(s);
+        if tables.is_empty() {
+            return Err(Error::InvalidText(0, "expect frames"));
+        }
+        let frames = tables
+            .iter()
+            .map(|t| parse_chars(t))
+            .collect::<Result<_>>()?;
+        Ok(Self(frames))
+    }
+}
+
+impl Display for AnimateComplexDiagram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, mx) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_char('\n')?;
+            }
+            write_table(f, mx.iter().cloned())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AnimateComplexDiagram {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let tables = tables(s);
+        if tables.is_empty() {
+            return Err(Error::InvalidText(0, "expect frames"));
+        }
+        let frames = tables
+            .iter()
+            .map(|t| parse_strings::<7, 7>(t, Self::CELL_CHARS_LIMIT))
+            .collect::<Result<_>>()?;
+        Ok(Self(frames))
+    }
+}
+
+/// Write cells as markdown table
+fn write_table<const W: usize>(
+    f: &mut Formatter<'_>,
+    rows: impl Iterator<Item = [Option<String>; W]>,
+) -> std::fmt::Result {
+    for (i, row) in rows.enumerate() {
+        for cell in row {
+            match cell.filter(|s| !s.is_empty()) {
+                Some(s) => write!(f, "|{}", escape(&s))?,
+                None => f.write_str("|  ")?,
+            }
+        }
+        writeln!(f, "|")?;
+        if i == 0 {
+            writeln!(f, "{}|", "|--".repeat(W))?;
+        }
+    }
+    Ok(())
+}
+
+/// Escape cell content
+fn escape(s: &str) -> String {
+    let last = s.chars().count() - 1;
+    s.chars()
+        .enumerate()
+        .fold(String::with_capacity(s.len()), |mut acc, (i, ch)| {
+            match ch {
+                '|' => acc.push_str("\\|"),
+                '\\' => acc.push_str("\\\\"),
+                ' ' if i == 0 || i == last => acc.push_str("\\s"),
+                '\t' => acc.push_str("\\t"),
+                '\n' => acc.push_str("\\n"),
+                '\r' => acc.push_str("\\r"),
+                _ if ch.is_control() => write!(acc, "\\u{{{:x}}}", ch as u32).unwrap(),
+                _ => acc.push(ch),
+            }
+            acc
+        })
+}
+
+/// Split text into tables of numbered lines, tables are separated by blank lines.
+fn tables(s: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut tables = vec![];
+    let mut table = vec![];
+    for (n, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !table.is_empty() {
+                tables.push(std::mem::take(&mut table));
+            }
+        } else {
+            table.push((n + 1, line));
+        }
+    }
+    if !table.is_empty() {
+        tables.push(table);
+    }
+    tables
+}
+
+/// Parse table of single character cells
+fn parse_chars<const H: usize, const W: usize>(
+    table: &[(usize, &str)],
+) -> Result<[[Option<char>; W]; H]> {
+    let mx = parse_table::<H, W>(table)?;
+    let mut chars = [[None; W]; H];
+    for (row, cells) in mx.into_iter().enumerate() {
+        for (col, cell) in cells.into_iter().enumerate() {
+            if let Some(s) = cell {
+                let mut it = s.chars();
+                if let (Some(ch), None) = (it.next(), it.next()) {
+                    chars[row][col] = Some(ch);
+                } else {
+                    return Err(Error::InvalidText(line_of(table, row), "expect one char"));
+                }
+            }
+        }
+    }
+    Ok(chars)
+}
+
+/// Parse table of string cells, at most `limit` characters each
+fn parse_strings<const H: usize, const W: usize>(
+    table: &[(usize, &str)],
+    limit: usize,
+) -> Result<[[Option<String>; W]; H]> {
+    let mx = parse_table::<H, W>(table)?;
+    for (n, row) in mx.iter().enumerate() {
+        if row.iter().flatten().any(|s| s.chars().count() > limit) {
+            return Err(Error::InvalidText(line_of(table, n), "cell too long"));
+        }
+    }
+    Ok(mx)
+}
+
+/// Line number of diagram row in table
+#[inline]
+fn line_of(table: &[(usize, &str)], row: usize) -> usize {
+    table[if row == 0 { 0 } else { row + 1 }].0
+}
+
+/// Parse table of h * w cells
+fn parse_table<const H: usize, const W: usize>(
+    table: &[(usize, &str)],
+) -> Result<[[Option<String>; W]; H]> {
+    let [first, separator, rest @ ..] = table else {
+        return Err(Error::InvalidText(
+            table.first().map_or(0, |v| v.0),
+            "rows count",
+        ));
+    };
+    if rest.len() + 1 != H {
+        return Err(Error::InvalidText(first.0, "rows count"));
+    }
+
+    let (n, line) = *separator;
+    let cells: Vec<&str> = line
+        .strip_prefix('|')
+        .and_then(|s| s.strip_suffix('|'))
+        .map(|s| s.split('|').collect())
+        .unwrap_or_default();
+    let valid = cells.len() == W
+        && cells.iter().all(|s| {
+            let s = s.trim_matches(' ');
+            s.contains('-') && s.chars().all(|ch| ch == '-' || ch == ':')
+        });
+    if !valid {
+        return Err(Error::InvalidText(n, "invalid separator"));
+    }
+
+    let mut mx = core::array::from_fn(|_| core::array::from_fn(|_| None));
+    for (row, &line) in std::iter::once(first).chain(rest).enumerate() {
+        mx[row] = parse_row::<W>(line)?;
+    }
+    Ok(mx)
+}
+
+/// Parse row of w cells
+fn parse_row<const W: usize>((n, line): (usize, &str)) -> Result<[Option<String>; W]> {
+    let line = line
+        .strip_prefix('|')
+        .ok_or(Error::InvalidText(n, "expect '|' at line start"))?;
+
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '|' => cells.push(std::mem::take(&mut cell)),
+            '\\' => {
+                cell.push(ch);
+                cell.push(
+                    chars
+                        .next()
+                        .ok_or(Error::InvalidText(n, "invalid escape"))?,
+                );
+            }
+            _ if ch.is_control() => return Err(Error::InvalidText(n, "control character")),
+            _ => cell.push(ch),
+        }
+    }
+    if !cell.is_empty() {
+        return Err(Error::InvalidText(n, "expect '|' at line end"));
+    }
+    if cells.len() != W {
+        return Err(Error::InvalidText(n, "columns count"));
+    }
+
+    let mut row = core::array::from_fn(|_| None);
+    for (i, cell) in cells.into_iter().enumerate() {
+        row[i] = unescape(cell.trim_matches(' ')).ok_or(Error::InvalidText(n, "invalid escape"))?;
+    }
+    Ok(row)
+}
+
+/// Unescape cell content, empty cell is `None`
+fn unescape(s: &str) -> Option<Option<String>> {
+    if s.is_empty() {
+        return Some(None);
+    }
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        result.push(match chars.next()? {
+            '|' => '|',
+            '\\' => '\\',
+            's' => ' ',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            'u' => {
+                let hex: String = chars.by_ref().take_while(|&ch| ch != '}').collect();
+                let hex = hex.strip_prefix('{')?;
+                if hex.is_empty() || hex.len() > 6 {
+                    return None;
+                }
+                char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+            }
+            _ => return None,
+        });
+    }
+    Some(Some(result))
+}
+
+#[cfg(test)]
+mod text_test {
+    use super::*;
+
+    const SIMPLE_TEXT: &str = "
+|  |  |  |  |  |  |  |
+|--|--|--|--|--|--|--|
+|  |🍔|  |  |  |🍟|  |
+|  |  |  |  |  |  |  |
+|  |  |  |🍩|  |  |  |
+|  |  |  |  |  |  |  |
+|  |🍦|  |  |  |🌭|  |
+|  |  |  |  |  |  |  |
+";
+
+    #[test]
+    fn test_simple_text() -> Result<()> {
+        let mut mx = [[None; 7]; 7];
+        mx[1][1] = Some('🍔');
+        mx[1][5] = Some('🍟');
+        mx[3][3] = Some('🍩');
+        mx[5][1] = Some('🍦');
+        mx[5][5] = Some('🌭');
+        let sdm = SimpleDiagram(mx);
+        assert_eq!(sdm.to_string(), SIMPLE_TEXT.trim_start());
+        assert_eq!(SIMPLE_TEXT.parse::<SimpleDiagram>()?, sdm);
+
+        let mut sdm = SimpleDiagram::<3, 4>::default();
+        sdm[0][0] = Some('|');
+        sdm[1][2] = Some(' ');
+        sdm[2][3] = Some('\\');
+        let text = sdm.to_string();
+        assert_eq!(text.lines().next(), Some(r"|\||  |  |  |"));
+        assert_eq!(text.parse::<SimpleDiagram<3, 4>>()?, sdm);
+
+        // size mismatch
+        let r = SIMPLE_TEXT.parse::<SimpleDiagram<5, 5>>();
+        assert!(matches!(r, Err(Error::InvalidText(2, _))));
+        let r = SIMPLE_TEXT.parse::<SimpleDiagram<7, 6>>();
+        assert!(matches!(r, Err(Error::InvalidText(3, _))));
+
+        // invalid cells
+        let r = SIMPLE_TEXT.replace("🍩", "🍩🍩").parse::<SimpleDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(6, _))));
+        let r = SIMPLE_TEXT.replace("🍩", r"\x").parse::<SimpleDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(6, _))));
+        let r = SIMPLE_TEXT.replace("🍩", "|").parse::<SimpleDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(6, _))));
+        let r = SIMPLE_TEXT
+            .replacen("|--", "|  ", 1)
+            .parse::<SimpleDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(3, _))));
+        let r = SIMPLE_TEXT
+            .replace("🍩", r"\u{d800}")
+            .parse::<SimpleDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(6, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_control_text() -> Result<()> {
+        let controls = ('\0'..='\u{1f}').chain('\u{7f}'..='\u{9f}');
+        let mut sdm = SimpleDiagram::<7, 10>::default();
+        let mut cdm = ComplexDiagram::<7, 10>::default();
+        for (i, ch) in controls.enumerate() {
+            sdm[i / 10][i % 10] = Some(ch);
+            cdm[i / 10][i % 10] = Some(format!("{ch}a{ch}b\u{1b}"));
+        }
+        let text = sdm.to_string();
+        assert!(!text.chars().any(|ch| ch.is_control() && ch != '\n'));
+        assert_eq!(text.parse::<SimpleDiagram<7, 10>>()?, sdm);
+        let text = cdm.to_string();
+        assert!(text.contains(r"|\u{1b}a\u{1b}b\u{1b}|"));
+        assert_eq!(text.parse::<ComplexDiagram<7, 10>>()?, cdm);
+        Ok(())
+    }
+
+    #[test]
+    fn test_complex_text() -> Result<()> {
+        let mut cdm = ComplexDiagram::<7, 7>::default();
+        cdm[0][0] = Some("ABC".to_owned());
+        cdm[2][3] = Some(" a|b\\c \t\n".to_owned());
+        cdm[6][6] = Some("A&*王😊".to_owned());
+        let text = cdm.to_string();
+        assert_eq!(
+            text.lines().nth(3),
+            Some(r"|  |  |  |\sa\|b\\c \t\n|  |  |  |")
+        );
+        assert_eq!(text.parse::<ComplexDiagram>()?, cdm);
+
+        let r = text
+            .replace("ABC", &"A".repeat(51))
+            .parse::<ComplexDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(1, _))));
+        let r = text.replace("ABC", "A\tB").parse::<ComplexDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(1, _))));
+        let r = format!("{text}\n{text}").parse::<ComplexDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(0, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_grapheme_text() -> Result<()> {
+        let mut gdm = GraphemeDiagram::<7, 7>::default();
+        gdm[1][1] = Some("🇺🇸".to_owned());
+        gdm[5][5] = Some("👨‍👩‍👧".to_owned());
+        let text = SIMPLE_TEXT.replace("🍔", "🇺🇸").replace("🌭", "👨‍👩‍👧");
+        let text = text.replace(['🍟', '🍩', '🍦'], " ");
+        assert_eq!(text.parse::<GraphemeDiagram>()?, gdm);
+        assert_eq!(gdm.to_string().parse::<GraphemeDiagram>()?, gdm);
+
+        // NFC normalized
+        gdm[3][3] = Some("é".to_owned());
+        let nfd = gdm.to_string().replace('é', "e\u{301}");
+        assert_eq!(nfd.parse::<GraphemeDiagram>()?, gdm);
+
+        let r = text.replace("🇺🇸", "ab").parse::<GraphemeDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(4, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_animate_complex_text() -> Result<()> {
+        let mut frames: Vec<[[Option<String>; 7]; 7]> = vec![Default::default(); 2];
+        frames[0][0][0] = Some("wake up".to_owned());
+        frames[1][6][6] = Some("a|b".to_owned());
+        let diagram = AnimateComplexDiagram(frames);
+        let text = diagram.to_string();
+        assert_eq!(text.lines().count(), 8 * 2 + 1);
+        assert_eq!(text.parse::<AnimateComplexDiagram>()?, diagram);
+
+        let r = text
+            .replace("wake up", &"z".repeat(51))
+            .parse::<AnimateComplexDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(1, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_animate_text() -> Result<()> {
+        let mut cube = vec![[[None; 7]; 7], [[Some('X'); 7]; 7]];
+        cube[0][3][3] = Some('🍩');
+        let diagram = AnimateDiagram(cube);
+        let text = diagram.to_string();
+        assert_eq!(text.lines().count(), 8 * 2 + 1);
+        assert_eq!(text.parse::<AnimateDiagram>()?, diagram);
+
+        let r = format!("{text}\n|  |").parse::<AnimateDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(19, _))));
+        let r = "\n\n".parse::<AnimateDiagram>();
+        assert!(matches!(r, Err(Error::InvalidText(0, _))));
+        Ok(())
+    }
+}
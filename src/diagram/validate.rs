@@ -0,0 +1,389 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Diagram input validation
+ *      inputs are checked in the order of diagram cells, index = row * 7 + col.
+ *      empty inputs are skipped, inputs beyond 49 are ignored by diagrams.
+ *
+ * [2] - Legacy versions
+ *      simple diagram keeps the first char of input.
+ *      complex diagram keeps the first 50 chars of input, no normalization.
+ *      grapheme diagram keeps the first grapheme of NFC normalized input.
+ *
+ * [3] - Cell contents
+ *      whitespace only cells, invisible or control chars are hard to check by eyes.
+ *      confusable chars are fullwidth forms, or Cyrillic/Greek letters looking like Latin,
+ *      reported when the cell has only one char or mixed with Latin letters.
+ *      cells longer than 255 bytes can't be serialized.
+**/
+use super::{ComplexDiagram, GraphemeDiagram};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Diagram input issue
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DiagramIssue {
+    /// Simple diagram keeps only the first char of input
+    CharTruncated {
+        /// Input index
+        index: usize,
+        /// Char kept by simple diagram
+        kept: char,
+    },
+    /// Complex diagram keeps only the first 50 chars of input
+    StringTruncated {
+        /// Input index
+        index: usize,
+        /// String kept by complex diagram
+        kept: String,
+    },
+    /// Grapheme diagram keeps only the first grapheme of input
+    GraphemeTruncated {
+        /// Input index
+        index: usize,
+        /// Grapheme kept by grapheme diagram
+        kept: String,
+    },
+    /// Input is not NFC normalized, simple and complex diagrams use it as is
+    Normalized {
+        /// Input index
+        index: usize,
+        /// NFC normalized input
+        normalized: String,
+    },
+    /// Non-empty inputs beyond 49 are ignored
+    Overflow {
+        /// Ignored non-empty inputs count, inputs beyond 98 are not checked
+        ignored: usize,
+    },
+    /// No content in diagram
+    EmptyDiagram,
+    /// Cell contains only whitespace
+    WhitespaceOnly {
+        /// Input index
+        index: usize,
+    },
+    /// Cell contains invisible or control char
+    InvisibleChar {
+        /// Input index
+        index: usize,
+        /// Invisible char
+        ch: char,
+    },
+    /// Cell contains char looking like another one
+    Confusable {
+        /// Input index
+        index: usize,
+        /// Confusable char
+        ch: char,
+        /// Char it looks like
+        like: char,
+    },
+    /// Cell is longer than 255 bytes
+    TooLong {
+        /// Input index
+        index: usize,
+        /// Bytes count of cell
+        bytes: usize,
+    },
+}
+
+/// Inputs checked, 49 cells and the same count of ignored inputs, iterators may be endless
+const INPUTS_CHECKED: usize = 7 * 7 * 2;
+
+/// Check issues of diagram inputs, up to `INPUTS_CHECKED`
+pub(super) fn check_inputs<T: AsRef<str>>(items: impl Iterator<Item = T>) -> Vec<DiagramIssue> {
+    const CELL_CHARS_LIMIT: usize = ComplexDiagram::<7, 7>::CELL_CHARS_LIMIT;
+    let mut issues = vec![];
+    let mut count = 0;
+    let mut ignored = 0;
+    for (index, s) in items.take(INPUTS_CHECKED).enumerate() {
+        let s = s.as_ref();
+        let Some(kept) = s.chars().next() else {
+            continue;
+        };
+        if index >= 7 * 7 {
+            ignored += 1;
+            continue;
+        }
+        count += 1;
+        if s.chars().nth(1).is_some() {
+            issues.push(DiagramIssue::CharTruncated { index, kept });
+        }
+        if s.chars().nth(CELL_CHARS_LIMIT).is_some() {
+            let kept = s.chars().take(CELL_CHARS_LIMIT).collect();
+            issues.push(DiagramIssue::StringTruncated { index, kept });
+        }
+        let normalized: String = s.nfc().collect();
+        if normalized.graphemes(true).nth(1).is_some() {
+            let kept = GraphemeDiagram::normalize(&normalized).unwrap_or_default();
+            issues.push(DiagramIssue::GraphemeTruncated { index, kept });
+        }
+        if let Some(bytes) = GraphemeDiagram::normalize(&normalized).map(|v| v.len())
+            && bytes > u8::MAX as usize
+        {
+            issues.push(DiagramIssue::TooLong { index, bytes });
+        }
+        if !is_nfc(s) {
+            issues.push(DiagramIssue::Normalized { index, normalized });
+        }
+        check_content(index, s, &mut issues);
+    }
+    if count == 0 {
+        issues.insert(0, DiagramIssue::EmptyDiagram);
+    }
+    if ignored > 0 {
+        issues.push(DiagramIssue::Overflow { ignored });
+    }
+    issues
+}
+
+/// Check issues of diagram cells, index = row * W + col
+pub(super) fn check_cells<T: AsRef<str>>(
+    cells: impl Iterator<Item = Option<T>>,
+) -> Vec<DiagramIssue> {
+    let mut issues = vec![];
+    let mut count = 0;
+    for (index, s) in cells.enumerate() {
+        let Some(s) = s.filter(|s| !s.as_ref().is_empty()) else {
+            continue;
+        };
+        let s = s.as_ref();
+        count += 1;
+        if s.len() > u8::MAX as usize {
+            let bytes = s.len();
+            issues.push(DiagramIssue::TooLong { index, bytes });
+        }
+        check_content(index, s, &mut issues);
+    }
+    if count == 0 {
+        issues.insert(0, DiagramIssue::EmptyDiagram);
+    }
+    issues
+}
+
+/// Check whitespace, invisible and confusable chars of cell
+fn check_content(index: usize, s: &str, issues: &mut Vec<DiagramIssue>) {
+    if s.chars().all(char::is_whitespace) {
+        issues.push(DiagramIssue::WhitespaceOnly { index });
+        return;
+    }
+
+    let mut found = vec![];
+    for ch in s.chars().filter(|&c| is_invisible(c)) {
+        if !found.contains(&ch) {
+            found.push(ch);
+            issues.push(DiagramIssue::InvisibleChar { index, ch });
+        }
+    }
+
+    let single = s.chars().nth(1).is_none();
+    let latin = s.chars().any(|c| c.is_ascii_alphabetic());
+    for ch in s.chars() {
+        let like = match confusable(ch) {
+            Some(like) if fullwidth(ch) || single || latin => like,
+            _ => continue,
+        };
+        if !found.contains(&ch) {
+            found.push(ch);
+            issues.push(DiagramIssue::Confusable { index, ch, like });
+        }
+    }
+}
+
+/// Control chars and invisible format chars, except joiners and selectors used by emoji
+fn is_invisible(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{00ad}'
+                | '\u{034f}'
+                | '\u{061c}'
+                | '\u{115f}'
+                | '\u{1160}'
+                | '\u{17b4}'
+                | '\u{17b5}'
+                | '\u{180b}'..='\u{180e}'
+                | '\u{200b}'
+                | '\u{200c}'
+                | '\u{200e}'
+                | '\u{200f}'
+                | '\u{202a}'..='\u{202e}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{206f}'
+                | '\u{3164}'
+                | '\u{feff}'
+                | '\u{ffa0}'
+                | '\u{fff0}'..='\u{fff8}'
+        )
+}
+
+#[inline]
+fn fullwidth(c: char) -> bool {
+    ('\u{ff01}'..='\u{ff5e}').contains(&c)
+}
+
+/// Latin char looked like, for fullwidth forms and Cyrillic/Greek homoglyphs
+fn confusable(c: char) -> Option<char> {
+    if fullwidth(c) {
+        return char::from_u32(c as u32 - 0xfee0);
+    }
+    const HOMOGLYPHS: &[(char, char)] = &[
+        // Cyrillic
+        ('а', 'a'),
+        ('е', 'e'),
+        ('о', 'o'),
+        ('р', 'p'),
+        ('с', 'c'),
+        ('у', 'y'),
+        ('х', 'x'),
+        ('ѕ', 's'),
+        ('і', 'i'),
+        ('ј', 'j'),
+        ('ԁ', 'd'),
+        ('һ', 'h'),
+        ('ԛ', 'q'),
+        ('ԝ', 'w'),
+        ('А', 'A'),
+        ('В', 'B'),
+        ('Е', 'E'),
+        ('К', 'K'),
+        ('М', 'M'),
+        ('Н', 'H'),
+        ('О', 'O'),
+        ('Р', 'P'),
+        ('С', 'C'),
+        ('Т', 'T'),
+        ('Х', 'X'),
+        ('Ѕ', 'S'),
+        ('І', 'I'),
+        ('Ј', 'J'),
+        // Greek
+        ('ο', 'o'),
+        ('ν', 'v'),
+        ('Α', 'A'),
+        ('Β', 'B'),
+        ('Ε', 'E'),
+        ('Ζ', 'Z'),
+        ('Η', 'H'),
+        ('Ι', 'I'),
+        ('Κ', 'K'),
+        ('Μ', 'M'),
+        ('Ν', 'N'),
+        ('Ο', 'O'),
+        ('Ρ', 'P'),
+        ('Τ', 'T'),
+        ('Υ', 'Y'),
+        ('Χ', 'X'),
+    ];
+    HOMOGLYPHS
+        .iter()
+        .find(|&&(v, _)| v == c)
+        .map(|&(_, like)| like)
+}
+
+#[cfg(test)]
+mod validate_test {
+    use super::*;
+    use DiagramIssue::*;
+
+    #[test]
+    fn test_check_inputs() {
+        let mut items = vec![""; 52];
+        items[0] = " ";
+        items[1] = "pаy"; // Cyrillic a
+        items[2] = "а";
+        items[3] = "да"; // Cyrillic word
+        items[4] = "ｂ";
+        items[5] = "a\u{200b}b";
+        items[6] = "👨\u{200d}👩\u{200d}👧";
+        items[50] = "x";
+        items[51] = "y";
+        let issues = check_inputs(items.iter());
+        assert_eq!(
+            issues,
+            [
+                WhitespaceOnly { index: 0 },
+                CharTruncated {
+                    index: 1,
+                    kept: 'p'
+                },
+                GraphemeTruncated {
+                    index: 1,
+                    kept: "p".to_owned()
+                },
+                Confusable {
+                    index: 1,
+                    ch: 'а',
+                    like: 'a'
+                },
+                Confusable {
+                    index: 2,
+                    ch: 'а',
+                    like: 'a'
+                },
+                CharTruncated {
+                    index: 3,
+                    kept: 'д'
+                },
+                GraphemeTruncated {
+                    index: 3,
+                    kept: "д".to_owned()
+                },
+                Confusable {
+                    index: 4,
+                    ch: 'ｂ',
+                    like: 'b'
+                },
+                CharTruncated {
+                    index: 5,
+                    kept: 'a'
+                },
+                GraphemeTruncated {
+                    index: 5,
+                    kept: "a".to_owned()
+                },
+                InvisibleChar {
+                    index: 5,
+                    ch: '\u{200b}'
+                },
+                CharTruncated {
+                    index: 6,
+                    kept: '👨'
+                },
+                Overflow { ignored: 2 },
+            ]
+        );
+        assert_eq!(check_inputs(["", ""].iter()), [EmptyDiagram]);
+    }
+
+    #[test]
+    fn test_check_cells() {
+        let mut cdm = ComplexDiagram::<3, 3>::default();
+        assert_eq!(cdm.validate(), [EmptyDiagram]);
+        cdm[0][1] = Some(String::new());
+        cdm[1][2] = Some("测".repeat(100));
+        cdm[2][0] = Some("\t".to_owned());
+        assert_eq!(
+            cdm.validate(),
+            [
+                TooLong {
+                    index: 5,
+                    bytes: 300
+                },
+                WhitespaceOnly { index: 6 }
+            ]
+        );
+
+        let mut mx = [[None; 7]; 7];
+        mx[1][1] = Some('\u{7}');
+        assert_eq!(
+            crate::SimpleDiagram(mx).validate(),
+            [InvisibleChar {
+                index: 8,
+                ch: '\u{7}'
+            }]
+        );
+    }
+}
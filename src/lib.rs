@@ -49,9 +49,17 @@
 mod bip38;
 mod bip39;
 mod bip85;
+mod chain;
 mod derive;
+mod descriptor;
 mod diagram;
 mod macros;
+mod multisig;
+mod progress;
+mod psbt;
+mod secret;
+mod slip132;
+mod watch;
 
 #[doc(no_inline)]
 pub use bitcoin::{self, bip32::Xpriv, bip32::Xpub};
@@ -59,9 +67,24 @@ pub use bitcoin::{self, bip32::Xpriv, bip32::Xpub};
 pub use bip38::{Bip38 as BIP38, MnemonicEncryption};
 pub use bip39::{Bip39 as BIP39, Language, Mnemonic};
 pub use bip85::{Bip85 as BIP85, Password, Wif};
-pub use derive::{Bip32 as BIP32, Bip44 as BIP44, Bip49 as BIP49, Bip84 as BIP84};
-pub use diagram::{AnimateDiagram, ComplexDiagram, Diagram, GenericDiagram, SimpleDiagram};
+pub use chain::AddressChain;
+pub use derive::{
+    Bip32 as BIP32, Bip44 as BIP44, Bip49 as BIP49, Bip84 as BIP84, Bip86 as BIP86, DerivedAccount,
+    DerivedMultisig, DerivedWallet,
+};
+pub use diagram::{
+    AnimateComplexDiagram, AnimateDiagram, Argon2Variant, CombineDiagram, Combiner, ComplexDiagram,
+    Diagram, DiagramIssue, DiagramRecovery, DiagramStrength, FingerprintStyle, GenericDiagram,
+    GraphemeDiagram, KdfParams, Recovery, RecoveryTarget, Share, SimpleDiagram, Strength,
+    StrengthWarning,
+};
+pub use multisig::{Bip48 as BIP48, Cosigner, MultisigScript, MultisigWallet};
+pub use psbt::{Bip174 as BIP174, PsbtError};
+pub use slip132::{ScriptType, Slip132, Slip132Priv, Slip132Pub};
+pub use watch::{WatchMultisig, WatchOnly};
 
+#[cfg(feature = "parallel")]
+pub use diagram::BatchDiagram;
 #[cfg(feature = "serde")]
 pub use diagram::{Matrix, ToMatrix};
 
@@ -95,6 +118,10 @@ pub mod error {
         /// Bip32 Error
         #[error("bip32 error: {0}")]
         Bip32Error(#[from] bitcoin::bip32::Error),
+
+        /// Psbt Error
+        #[error("PSBT: {0}")]
+        PsbtError(#[from] crate::psbt::PsbtError),
     }
 }
 pub use error::Error;
@@ -105,3 +132,16 @@ pub const NETWORK: bitcoin::NetworkKind = bitcoin::NetworkKind::Main;
 /// Bitcoin network
 #[cfg(feature = "testnet")]
 pub const NETWORK: bitcoin::NetworkKind = bitcoin::NetworkKind::Test;
+
+/// Default network of masters, `_in` functions accept other networks
+///
+/// Derivation from an existing master follows the network of the key, not this default:
+/// coin type of paths is 1 for `tprv` and 0 for `xprv`, whatever the `testnet` feature is.
+#[cfg(not(feature = "testnet"))]
+pub const DEFAULT_NETWORK: bitcoin::Network = bitcoin::Network::Bitcoin;
+/// Default network of masters, `_in` functions accept other networks
+///
+/// Derivation from an existing master follows the network of the key, not this default:
+/// coin type of paths is 1 for `tprv` and 0 for `xprv`, whatever the `testnet` feature is.
+#[cfg(feature = "testnet")]
+pub const DEFAULT_NETWORK: bitcoin::Network = bitcoin::Network::Testnet;
@@ -0,0 +1,318 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Cosigners
+ *      a cosigner is an account xpub with key origin,
+ *      parsed from or formatted as `[fingerprint/path]xpub`.
+ *      SLIP-132 keys (`Ypub`, `Zpub`...) exported by Coldcard or Sparrow are also accepted.
+ *
+ * [2] - Multisig wallet
+ *      own BIP48 account and foreign cosigners, sorted multisig of all keys.
+ *      receive and change addresses are derived from xpubs: `xpub/change/index`.
+ *
+ * [3] - Export
+ *      output descriptor and Coldcard multisig setup file, also imported by Sparrow.
+ *
+ * # Reference
+ * [1] - [BIP48 spec](https://bips.dev/48/)
+ * [2] - [Coldcard multisig](https://coldcard.com/docs/multisig/)
+**/
+use crate::derive::{
+    DeriveError, DeriveInner, DerivedAccount, DerivedMultisig, coin, multisig_script,
+};
+use crate::descriptor::{sortedmulti, with_checksum};
+use crate::slip132::Slip132Pub;
+use bitcoin::{
+    Address, Network, NetworkKind, PublicKey,
+    bip32::{ChildNumber, DerivationPath, KeySource, Xpub},
+    key::Secp256k1,
+};
+use std::str::FromStr;
+
+/// Script type of BIP48 multisig
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultisigScript {
+    /// Nested segwit, script type 1'
+    P2shP2wsh = 1,
+    /// Native segwit, script type 2'
+    P2wsh = 2,
+}
+
+/// Multisig cosigner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cosigner {
+    /// Account extended public key
+    pub xpub: Xpub,
+    /// Key origin: fingerprint of master and derivation path
+    pub origin: KeySource,
+}
+
+impl From<&DerivedAccount> for Cosigner {
+    fn from(account: &DerivedAccount) -> Self {
+        Cosigner {
+            xpub: account.xpub,
+            origin: account.origin.clone(),
+        }
+    }
+}
+
+impl FromStr for Cosigner {
+    type Err = DeriveError;
+
+    /// Parse cosigner from `[fingerprint/path]xpub` or `[fingerprint]xpub`,
+    /// optional suffix `/<0;1>/*`, xpub of any SLIP-132 prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DeriveError::InvalidCosigner(s.to_owned());
+        let (origin, xpub) = s
+            .strip_prefix('[')
+            .and_then(|s| s.split_once(']'))
+            .ok_or_else(invalid)?;
+        let (fingerprint, path) = match origin.split_once('/') {
+            Some((fingerprint, path)) => (fingerprint, path.parse().map_err(|_| invalid())?),
+            None => (origin, DerivationPath::master()),
+        };
+        let xpub = xpub.strip_suffix("/<0;1>/*").unwrap_or(xpub);
+        Ok(Cosigner {
+            xpub: xpub.parse::<Slip132Pub>().map_err(|_| invalid())?.xpub,
+            origin: (fingerprint.parse().map_err(|_| invalid())?, path),
+        })
+    }
+}
+
+impl std::fmt::Display for Cosigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (fingerprint, path) = &self.origin;
+        match path.is_empty() {
+            true => write!(f, "[{fingerprint}]{}", self.xpub),
+            false => write!(f, "[{fingerprint}/{path}]{}", self.xpub),
+        }
+    }
+}
+
+/// Multisig wallet of cosigners
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigWallet {
+    /// Number of required signatures
+    pub threshold: u8,
+    /// Script type
+    pub script: MultisigScript,
+    /// Cosigners, own account first
+    pub cosigners: Vec<Cosigner>,
+    /// Network of addresses
+    pub network: Network,
+}
+
+impl MultisigWallet {
+    /// Create a multisig wallet
+    /// # Errors
+    ///   threshold or cosigners out of `1 <= threshold <= cosigners <= 15`,
+    ///   duplicate cosigners, cosigner of other network.
+    pub fn new(
+        threshold: u8,
+        script: MultisigScript,
+        cosigners: Vec<Cosigner>,
+        network: Network,
+    ) -> Result<Self, DeriveError> {
+        if threshold < 1 || threshold as usize > cosigners.len() || cosigners.len() > 15 {
+            return Err(DeriveError::InvalidMultisig("1 <= M <= N <= 15"));
+        }
+        let duplicate =
+            |(i, c): (usize, &Cosigner)| cosigners[..i].iter().any(|x| x.xpub == c.xpub);
+        if cosigners.iter().enumerate().any(duplicate) {
+            return Err(DeriveError::InvalidMultisig("duplicate cosigner"));
+        }
+        if let Some(c) = cosigners
+            .iter()
+            .find(|c| c.xpub.network != NetworkKind::from(network))
+        {
+            return Err(DeriveError::InvalidCosigner(c.to_string()));
+        }
+        Ok(MultisigWallet {
+            threshold,
+            script,
+            cosigners,
+            network,
+        })
+    }
+
+    /// Derive a receive or change address
+    /// # Derivation path
+    ///   xpub/0/index, xpub/1/index for change
+    /// # Returns
+    ///   address, witness script, key origins of cosigners
+    pub fn derive(&self, index: u32, change: bool) -> Result<DerivedMultisig, DeriveError> {
+        let secp = Secp256k1::verification_only();
+        let path = [
+            ChildNumber::from_normal_idx(change as u32)?,
+            ChildNumber::from_normal_idx(index)?,
+        ];
+        let (pub_keys, origins): (Vec<_>, Vec<_>) = self
+            .cosigners
+            .iter()
+            .map(|c| {
+                let xpub = c.xpub.derive_pub(&secp, &path)?;
+                Ok((
+                    PublicKey::new(xpub.public_key),
+                    (c.origin.0, c.origin.1.extend(path)),
+                ))
+            })
+            .collect::<Result<Vec<_>, DeriveError>>()?
+            .into_iter()
+            .unzip();
+        let script = multisig_script(self.threshold, pub_keys);
+        let address = match self.script {
+            MultisigScript::P2shP2wsh => Address::p2shwsh(&script, self.network),
+            MultisigScript::P2wsh => Address::p2wsh(&script, self.network),
+        };
+        Ok(DerivedMultisig {
+            address,
+            script,
+            origins,
+        })
+    }
+
+    /// Output descriptor of receive and change addresses
+    /// # Returns
+    ///   `wsh(sortedmulti(M,[fingerprint/path]xpub/<0;1>/*,...))#checksum`,
+    ///   wrapped in `sh(...)` for p2sh-p2wsh
+    pub fn descriptor(&self) -> String {
+        let keys = self.cosigners.iter().map(|c| format!("{c}/<0;1>/*"));
+        let multi = sortedmulti(self.threshold, keys);
+        with_checksum(match self.script {
+            MultisigScript::P2shP2wsh => format!("sh(wsh({multi}))"),
+            MultisigScript::P2wsh => format!("wsh({multi})"),
+        })
+    }
+
+    /// Coldcard multisig setup file, also imported by Sparrow
+    /// # Parameters
+    ///   `name`: wallet name, Coldcard accepts 20 characters at most
+    pub fn coldcard_config(&self, name: &str) -> String {
+        let format = match self.script {
+            MultisigScript::P2shP2wsh => "P2SH-P2WSH",
+            MultisigScript::P2wsh => "P2WSH",
+        };
+        let derivation = |c: &Cosigner| {
+            let path: String = c.origin.1.into_iter().map(|n| format!("/{n}")).collect();
+            format!("Derivation: m{path}\n")
+        };
+        let same_path = self
+            .cosigners
+            .windows(2)
+            .all(|w| w[0].origin.1 == w[1].origin.1);
+
+        let mut config = format!(
+            "# Coldcard Multisig setup file (created by Artimonist)\n#\nName: {name}\nPolicy: {} of {}\n",
+            self.threshold,
+            self.cosigners.len()
+        );
+        if let Some(c) = self.cosigners.first().filter(|_| same_path) {
+            config += &derivation(c);
+        }
+        config += &format!("Format: {format}\n\n");
+        for c in &self.cosigners {
+            if !same_path {
+                config += &derivation(c);
+            }
+            let fingerprint = c.origin.0.to_string().to_uppercase();
+            config += &format!("{fingerprint}: {}\n", c.xpub);
+        }
+        config
+    }
+}
+
+/// BIP48 derivation
+/// Multi-signature wallets of shared custody, with foreign cosigners.
+pub trait Bip48
+where
+    Self: DeriveInner,
+{
+    /// Derive a BIP48 account
+    /// # Derivation path
+    ///   m/48'/0'/account'/script', script is 1 for p2sh-p2wsh, 2 for p2wsh
+    /// # Returns
+    ///   (xpub, xpriv)
+    fn bip48_account(
+        &self,
+        account: u32,
+        script: MultisigScript,
+    ) -> Result<(String, String), DeriveError> {
+        self.bip48_derive_account(account, script)
+            .map(|account| (account.xpub.to_string(), account.xpriv.to_string()))
+    }
+
+    /// Derive a BIP48 account with key origin
+    fn bip48_derive_account(
+        &self,
+        account: u32,
+        script: MultisigScript,
+    ) -> Result<DerivedAccount, DeriveError> {
+        let coin = coin(self.network());
+        self.derive(&format!("m/48'/{coin}'/{account}'/{}'", script as u8))
+    }
+
+    /// Create a multisig wallet of own BIP48 account and foreign cosigners
+    /// # Parameters
+    /// - `threshold`: Number of required signatures
+    /// - `account`: Own account index
+    /// - `script`: Script type
+    /// - `cosigners`: Foreign cosigners, `[fingerprint/path]xpub` of other devices
+    fn bip48_multisig(
+        &self,
+        threshold: u8,
+        account: u32,
+        script: MultisigScript,
+        cosigners: &[Cosigner],
+    ) -> Result<MultisigWallet, DeriveError> {
+        let own = Cosigner::from(&self.bip48_derive_account(account, script)?);
+        let cosigners = std::iter::once(own)
+            .chain(cosigners.iter().cloned())
+            .collect();
+        MultisigWallet::new(threshold, script, cosigners, self.network())
+    }
+}
+
+impl Bip48 for bitcoin::bip32::Xpriv {}
+
+#[cfg(test)]
+mod multisig_test {
+    use super::*;
+
+    #[test]
+    fn test_cosigner() {
+        const KEY: &str = "[73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+        let cosigner = Cosigner::from_str(KEY).unwrap();
+        assert_eq!(cosigner.to_string(), KEY);
+        assert_eq!(
+            Cosigner::from_str(&format!("{KEY}/<0;1>/*")).unwrap(),
+            cosigner
+        );
+        let hardened = KEY.replace('\'', "h");
+        assert_eq!(Cosigner::from_str(&hardened).unwrap(), cosigner);
+
+        // SLIP-132 key exported by Coldcard or Sparrow
+        const ZPUB: &str = "Zpub739WFCnqb8H6bozqRWNgL4NwrVvUUDaa5UodTovoPXuLnoVJTvkwKA6b5ioUif4ntuhU53ob9LUdZ66F3uNGoX8S6gzjGa1yvYFtkDRknR2";
+        let zpub = Cosigner::from_str(&format!("[73c5da0a/84'/0'/0']{ZPUB}/<0;1>/*")).unwrap();
+        assert_eq!(zpub, cosigner);
+
+        // fingerprint only origin
+        let (_, xpub) = KEY.split_once(']').unwrap();
+        let key = format!("[73c5da0a]{xpub}");
+        let master = Cosigner::from_str(&key).unwrap();
+        assert_eq!(master.xpub, cosigner.xpub);
+        assert!(master.origin.1.is_empty());
+        assert_eq!(master.to_string(), key);
+
+        for invalid in [
+            &KEY[1..],
+            &KEY[..KEY.len() - 1],
+            "[73c5da0a]xpub",
+            "[73c5da0a/]xpub",
+        ] {
+            assert!(matches!(
+                Cosigner::from_str(invalid),
+                Err(DeriveError::InvalidCosigner(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,247 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Key stretching progress
+ *      callback receives (done, total) work units, returns `false` to cancel.
+ *      one work unit is 128 bytes of memory mixing:
+ *        scrypt: 2 * N * r * p units, reported every 1024 BlockMix operations
+ *        argon2: 8 * m_cost * t_cost units, reported when finished
+ *        pbkdf2: rounds units, reported when finished
+ *      argon2 and pbkdf2 have no hook inside a pass, they can't be cancelled while running,
+ *      cancellation takes effect after them.
+ *
+ * [2] - scrypt
+ *      without progress callback, the audited `scrypt::scrypt` is used.
+ *      with progress callback, same algorithm as `scrypt::scrypt`, p lanes are mixed one by one.
+ *      outputs are compared with `scrypt::scrypt` at V1 and V2 parameters, in an ignored slow test.
+**/
+use crate::secret::protect;
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+
+/// Optional progress callback, receives (done, total) work units, returns `false` to cancel
+pub(crate) type Progress<'a> = Option<&'a mut dyn FnMut(u64, u64) -> bool>;
+
+/// Key stretching error
+#[derive(Debug)]
+pub(crate) enum StretchError {
+    /// Cancelled by progress callback
+    Cancelled,
+    /// Invalid scrypt output length
+    OutputLen(scrypt::errors::InvalidOutputLen),
+}
+
+/// Track work units of key stretching
+pub(crate) struct Tracker<'a> {
+    callback: Progress<'a>,
+    done: u64,
+    total: u64,
+}
+
+impl<'a> Tracker<'a> {
+    pub fn new(callback: Progress<'a>, total: u64) -> Self {
+        Self {
+            callback,
+            done: 0,
+            total,
+        }
+    }
+
+    /// Work units of scrypt
+    pub fn scrypt_units(params: &scrypt::Params) -> u64 {
+        2 * (1_u64 << params.log_n()) * params.r() as u64 * params.p() as u64
+    }
+
+    /// Work units of argon2
+    pub fn argon2_units(params: &argon2::Params) -> u64 {
+        8 * params.m_cost() as u64 * params.t_cost() as u64
+    }
+
+    /// Advance work units and report progress
+    pub fn advance(&mut self, units: u64) -> Result<(), StretchError> {
+        self.done = (self.done + units).min(self.total);
+        let (done, total) = (self.done, self.total);
+        match self
+            .callback
+            .as_mut()
+            .is_none_or(|callback| callback(done, total))
+        {
+            true => Ok(()),
+            false => Err(StretchError::Cancelled),
+        }
+    }
+
+    /// scrypt key derivation, with progress if callback is present
+    pub fn scrypt(
+        &mut self,
+        password: &[u8],
+        salt: &[u8],
+        params: &scrypt::Params,
+        output: &mut [u8],
+    ) -> Result<(), StretchError> {
+        if self.callback.is_none() {
+            return scrypt::scrypt(password, salt, params, output).map_err(StretchError::OutputLen);
+        }
+        self.advance(0)?;
+        let n = 1 << params.log_n();
+        let r128 = params.r() as usize * 128;
+        let pr128 = params.p() as usize * r128;
+
+        let mut b = protect(vec![0u8; pr128]);
+        pbkdf2_hmac::<Sha256>(password, salt, 1, &mut b);
+
+        let mut v = protect(vec![0u8; n * r128]);
+        let mut t = protect(vec![0u8; r128]);
+        for chunk in b.chunks_mut(r128) {
+            self.ro_mix(chunk, &mut v, &mut t, n)?;
+        }
+
+        pbkdf2_hmac::<Sha256>(password, &b, 1, output);
+        Ok(())
+    }
+
+    /// ROMix operation in-place, reports progress every 1024 BlockMix operations
+    fn ro_mix(
+        &mut self,
+        b: &mut [u8],
+        v: &mut [u8],
+        t: &mut [u8],
+        n: usize,
+    ) -> Result<(), StretchError> {
+        const STEPS: usize = 1024;
+        let len = b.len();
+        let units = STEPS as u64 * (len / 128) as u64;
+
+        for (i, chunk) in v.chunks_mut(len).enumerate() {
+            chunk.copy_from_slice(b);
+            block_mix(chunk, b);
+            if (i + 1).is_multiple_of(STEPS) {
+                self.advance(units)?;
+            }
+        }
+
+        for i in 0..n {
+            // n is a power of 2, mod n is a bit mask
+            let x = u32::from_le_bytes(b[len - 64..len - 60].try_into().unwrap());
+            let j = x as usize & (n - 1);
+            xor(b, &v[j * len..(j + 1) * len], t);
+            block_mix(t, b);
+            if (i + 1).is_multiple_of(STEPS) {
+                self.advance(units)?;
+            }
+        }
+
+        // remaining units of small N
+        if !n.is_multiple_of(STEPS) {
+            self.advance(2 * (n % STEPS) as u64 * (len / 128) as u64)?;
+        }
+        Ok(())
+    }
+}
+
+/// BlockMix operation, input length must be a multiple of 128
+fn block_mix(input: &[u8], output: &mut [u8]) {
+    use salsa20::{
+        SalsaCore,
+        cipher::{StreamCipherCore, typenum::U4},
+    };
+    type Salsa20_8 = SalsaCore<U4>;
+
+    let mut x = [0u8; 64];
+    x.copy_from_slice(&input[input.len() - 64..]);
+
+    let mut t = [0u8; 64];
+    for (i, chunk) in input.chunks(64).enumerate() {
+        xor(&x, chunk, &mut t);
+
+        let mut state = [0u32; 16];
+        for (c, v) in t.chunks_exact(4).zip(state.iter_mut()) {
+            *v = u32::from_le_bytes(c.try_into().unwrap());
+        }
+        Salsa20_8::from_raw_state(state).write_keystream_block((&mut x).into());
+
+        let pos = match i % 2 {
+            0 => (i / 2) * 64,
+            _ => (i / 2) * 64 + input.len() / 2,
+        };
+        output[pos..pos + 64].copy_from_slice(&x);
+    }
+}
+
+#[inline]
+fn xor(x: &[u8], y: &[u8], output: &mut [u8]) {
+    output
+        .iter_mut()
+        .zip(x.iter().zip(y))
+        .for_each(|(out, (a, b))| *out = a ^ b);
+}
+
+#[cfg(test)]
+mod progress_test {
+    use super::*;
+
+    #[test]
+    fn test_scrypt_progress() -> Result<(), StretchError> {
+        for (log_n, r, p) in [(10, 8, 1), (14, 8, 8), (4, 1, 1), (12, 2, 3)] {
+            let params = scrypt::Params::new(log_n, r, p, 64).unwrap();
+            let mut expected = [0u8; 64];
+            scrypt::scrypt(b"password", b"NaCl", &params, &mut expected).unwrap();
+
+            let total = Tracker::scrypt_units(&params);
+            let mut last = (0, 0);
+            let mut callback = |done, total| {
+                assert!(done >= last.0);
+                last = (done, total);
+                true
+            };
+            let mut output = [0u8; 64];
+            Tracker::new(Some(&mut callback), total).scrypt(
+                b"password",
+                b"NaCl",
+                &params,
+                &mut output,
+            )?;
+            assert_eq!(output, expected);
+            assert_eq!(last, (total, total));
+        }
+
+        // cancel at half
+        let params = scrypt::Params::new(14, 8, 1, 64).unwrap();
+        let total = Tracker::scrypt_units(&params);
+        let mut callback = |done, total| done < total / 2;
+        let r = Tracker::new(Some(&mut callback), total).scrypt(b"", b"", &params, &mut [0u8; 64]);
+        assert!(matches!(r, Err(StretchError::Cancelled)));
+
+        // audited scrypt without callback
+        let mut output = [0u8; 64];
+        Tracker::new(None, 0).scrypt(b"password", b"NaCl", &params, &mut output)?;
+        let mut expected = [0u8; 64];
+        scrypt::scrypt(b"password", b"NaCl", &params, &mut expected).unwrap();
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "slow, V2 uses 1 GiB"]
+    fn test_scrypt_kdf_params() -> Result<(), StretchError> {
+        use crate::KdfParams;
+        for kdf in [KdfParams::V1, KdfParams::V2] {
+            let params =
+                scrypt::Params::new(kdf.scrypt_log_n, kdf.scrypt_r, kdf.scrypt_p, 32).unwrap();
+            let mut expected = [0u8; 32];
+            scrypt::scrypt(b"password", b"salt", &params, &mut expected).unwrap();
+
+            let total = Tracker::scrypt_units(&params);
+            let mut callback = |_, _| true;
+            let mut output = [0u8; 32];
+            Tracker::new(Some(&mut callback), total).scrypt(
+                b"password",
+                b"salt",
+                &params,
+                &mut output,
+            )?;
+            assert_eq!(output, expected);
+        }
+        Ok(())
+    }
+}
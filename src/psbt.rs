@@ -0,0 +1,82 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Offline signing
+ *      inputs are matched by key origins of master fingerprint:
+ *      `bip32_derivation` for ecdsa, `tap_key_origins` for schnorr.
+ *      inputs of other signers are skipped, no utxo or script needed for them.
+ *
+ * [2] - Script types
+ *      p2pkh, p2sh-p2wpkh, p2wpkh, p2tr key path,
+ *      multisig of p2sh, p2sh-p2wsh and p2wsh with redeem or witness script.
+ *
+ * # Reference
+ * [1] - [BIP174](https://bips.dev/174/)
+ * [2] - [BIP371](https://bips.dev/371/)
+**/
+use bitcoin::{
+    bip32::{Fingerprint, Xpriv},
+    key::Secp256k1,
+    psbt::{Input, Psbt},
+};
+
+/// BIP174 signing
+///
+/// # Examples
+/// ```no_run
+/// use artimonist::{BIP39, BIP174};
+/// use artimonist::bitcoin::Psbt;
+/// # use std::str::FromStr;
+///
+/// let master = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".mnemonic_to_master("")?;
+/// let psbt = Psbt::from_str("cHNidP8BAH...")?;
+/// let signed = master.bip174_sign(psbt)?;
+/// println!("{signed}");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait Bip174 {
+    /// Sign inputs of master fingerprint
+    /// # Returns
+    ///   psbt with signatures in `partial_sigs`, `tap_key_sig`
+    fn bip174_sign(&self, psbt: Psbt) -> Result<Psbt, PsbtError>;
+}
+
+impl Bip174 for Xpriv {
+    fn bip174_sign(&self, mut psbt: Psbt) -> Result<Psbt, PsbtError> {
+        let secp = Secp256k1::new();
+        let fingerprint = self.fingerprint(&secp);
+        let matched = |input: &Input| {
+            input
+                .bip32_derivation
+                .values()
+                .any(|(fp, _)| *fp == fingerprint)
+                || input
+                    .tap_key_origins
+                    .values()
+                    .any(|(_, (fp, _))| *fp == fingerprint)
+        };
+        if !psbt.inputs.iter().any(matched) {
+            return Err(PsbtError::NoMatchingInput(fingerprint));
+        }
+
+        if let Err((_, errors)) = psbt.sign(self, &secp) {
+            // errors of other signers' inputs are ignored
+            let mine = errors.into_iter().find(|(i, _)| matched(&psbt.inputs[*i]));
+            if let Some((index, e)) = mine {
+                return Err(PsbtError::SignInput(index, e));
+            }
+        }
+        Ok(psbt)
+    }
+}
+
+/// PSBT signing error
+#[derive(thiserror::Error, Debug)]
+pub enum PsbtError {
+    /// No input of master
+    #[error("no input of fingerprint: {0}")]
+    NoMatchingInput(Fingerprint),
+    /// Sign input error
+    #[error("sign input {0}: {1}")]
+    SignInput(usize, bitcoin::psbt::SignError),
+}
@@ -0,0 +1,93 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Secret buffers
+ *      with `zeroize` feature, secret buffers are wiped on drop.
+ *      without it, secret buffers are plain values, no extra cost.
+ *
+ * [2] - Secret types
+ *      public types holding secrets are wiped on drop, `Debug` output is redacted.
+ *      fields of them can't be moved out, clone them instead.
+**/
+
+/// Secret buffer, wiped on drop with `zeroize` feature
+#[cfg(feature = "zeroize")]
+pub(crate) type Secret<T> = zeroize::Zeroizing<T>;
+/// Secret buffer, wiped on drop with `zeroize` feature
+#[cfg(not(feature = "zeroize"))]
+pub(crate) type Secret<T> = T;
+
+/// Protect value as secret buffer
+#[cfg(feature = "zeroize")]
+#[inline]
+pub(crate) fn protect<T: zeroize::Zeroize>(value: T) -> Secret<T> {
+    zeroize::Zeroizing::new(value)
+}
+/// Protect value as secret buffer
+#[cfg(not(feature = "zeroize"))]
+#[inline(always)]
+pub(crate) fn protect<T>(value: T) -> Secret<T> {
+    value
+}
+
+/// impl Drop and redacted Debug for secret type, fields are wiped on drop
+macro_rules! ImpSecret {
+    ($t: ident $(<$(const $g: ident),+>)?, $($field: tt),+) => {
+        #[cfg(feature = "zeroize")]
+        impl$(<$(const $g: usize),+>)? Drop for $t$(<$($g),+>)? {
+            fn drop(&mut self) {
+                $(zeroize::Zeroize::zeroize(&mut self.$field);)+
+            }
+        }
+        #[cfg(feature = "zeroize")]
+        impl$(<$(const $g: usize),+>)? zeroize::ZeroizeOnDrop for $t$(<$($g),+>)? {}
+        #[cfg(feature = "zeroize")]
+        impl$(<$(const $g: usize),+>)? std::fmt::Debug for $t$(<$($g),+>)? {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($t)).finish_non_exhaustive()
+            }
+        }
+    };
+}
+pub(crate) use ImpSecret;
+
+#[cfg(feature = "zeroize")]
+#[cfg(test)]
+mod secret_test {
+    use crate::*;
+
+    #[test]
+    fn test_zeroize_on_drop() {
+        fn wiped<T: zeroize::ZeroizeOnDrop>() {}
+        wiped::<Mnemonic>();
+        wiped::<Wif>();
+        wiped::<Share>();
+        wiped::<SimpleDiagram<5, 9>>();
+        wiped::<ComplexDiagram>();
+        wiped::<GraphemeDiagram>();
+        wiped::<AnimateDiagram>();
+        wiped::<AnimateComplexDiagram>();
+    }
+
+    #[test]
+    fn test_redacted_debug() {
+        let mnemonic: Mnemonic = "theme rain hollow final expire proud detect wife hotel taxi witness strategy park head forest".parse().unwrap();
+        assert_eq!(format!("{mnemonic:?}"), "Mnemonic { .. }");
+
+        let mut mx = [[None; 7]; 7];
+        mx[3][3] = Some('🍩');
+        assert_eq!(format!("{:?}", SimpleDiagram(mx)), "SimpleDiagram { .. }");
+
+        let wif = Wif {
+            pk: "L3X74sTsPBpoJx7MNWt36qnsGE1ubdf2BQVTczU7i14D94BhNdmc".to_owned(),
+            addr: String::new(),
+        };
+        assert!(!format!("{wif:?}").contains(&wif.pk));
+
+        let share = Share {
+            index: 1,
+            data: [0xa5; 32],
+        };
+        assert_eq!(format!("{share:?}"), "Share { .. }");
+    }
+}
@@ -0,0 +1,284 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - SLIP-132 prefixes
+ *      extended keys are the same BIP32 keys with other version bytes,
+ *      version bytes imply the script type of derived addresses.
+ *
+ * [2] - Conversion
+ *      parse any prefix of mainnet and testnet, emit with any other prefix.
+ *
+ * # Reference
+ * [1] - [SLIP-132](https://github.com/satoshilabs/slips/blob/master/slip-0132.md)
+**/
+use crate::derive::DeriveError;
+use bitcoin::{
+    NetworkKind,
+    bip32::{Xpriv, Xpub},
+};
+use std::str::FromStr;
+
+/// Script type of addresses, implied by SLIP-132 prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptType {
+    /// p2pkh, or legacy p2sh multisig
+    P2pkh,
+    /// p2sh-p2wpkh
+    P2shP2wpkh,
+    /// p2wpkh
+    P2wpkh,
+    /// p2sh-p2wsh multisig
+    P2shP2wsh,
+    /// p2wsh multisig
+    P2wsh,
+    /// key-path only p2tr, no SLIP-132 prefix
+    P2tr,
+}
+
+/// SLIP-132 prefix of extended keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Slip132 {
+    /// xpub, xprv; tpub, tprv
+    X,
+    /// ypub, yprv; upub, uprv
+    Y,
+    /// zpub, zprv; vpub, vprv
+    Z,
+    /// Ypub, Yprv; Upub, Uprv
+    MultiY,
+    /// Zpub, Zprv; Vpub, Vprv
+    MultiZ,
+}
+
+/// Version bytes: (prefix, mainnet public, mainnet private, testnet public, testnet private)
+const VERSIONS: [(Slip132, u32, u32, u32, u32); 5] = [
+    (Slip132::X, 0x0488b21e, 0x0488ade4, 0x043587cf, 0x04358394),
+    (Slip132::Y, 0x049d7cb2, 0x049d7878, 0x044a5262, 0x044a4e28),
+    (Slip132::Z, 0x04b24746, 0x04b2430c, 0x045f1cf6, 0x045f18bc),
+    (
+        Slip132::MultiY,
+        0x0295b43f,
+        0x0295b005,
+        0x024289ef,
+        0x024285b5,
+    ),
+    (
+        Slip132::MultiZ,
+        0x02aa7ed3,
+        0x02aa7a99,
+        0x02575483,
+        0x02575048,
+    ),
+];
+
+impl Slip132 {
+    /// Script type implied by prefix
+    pub fn script_type(self) -> ScriptType {
+        match self {
+            Slip132::X => ScriptType::P2pkh,
+            Slip132::Y => ScriptType::P2shP2wpkh,
+            Slip132::Z => ScriptType::P2wpkh,
+            Slip132::MultiY => ScriptType::P2shP2wsh,
+            Slip132::MultiZ => ScriptType::P2wsh,
+        }
+    }
+
+    /// Version bytes of prefix
+    fn version(self, network: NetworkKind, private: bool) -> u32 {
+        let (_, main_pub, main_prv, test_pub, test_prv) = VERSIONS[self as usize];
+        match (network, private) {
+            (NetworkKind::Main, false) => main_pub,
+            (NetworkKind::Main, true) => main_prv,
+            (NetworkKind::Test, false) => test_pub,
+            (NetworkKind::Test, true) => test_prv,
+        }
+    }
+
+    /// Prefix, network and is private of version bytes
+    fn from_version(version: u32) -> Option<(Slip132, NetworkKind, bool)> {
+        VERSIONS.iter().find_map(
+            |&(prefix, main_pub, main_prv, test_pub, test_prv)| match version {
+                v if v == main_pub => Some((prefix, NetworkKind::Main, false)),
+                v if v == main_prv => Some((prefix, NetworkKind::Main, true)),
+                v if v == test_pub => Some((prefix, NetworkKind::Test, false)),
+                v if v == test_prv => Some((prefix, NetworkKind::Test, true)),
+                _ => None,
+            },
+        )
+    }
+}
+
+/// Decode extended key of any prefix, version bytes replaced by standard xpub/xprv
+fn decode(s: &str, private: bool) -> Result<(Slip132, [u8; 78]), DeriveError> {
+    let invalid = || DeriveError::InvalidAccount(s.to_owned());
+    let data = bitcoin::base58::decode_check(s).map_err(|_| invalid())?;
+    let mut data: [u8; 78] = data.try_into().map_err(|_| invalid())?;
+    let version = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+    match Slip132::from_version(version) {
+        Some((prefix, network, is_private)) if is_private == private => {
+            let standard = Slip132::X.version(network, private);
+            data[..4].copy_from_slice(&standard.to_be_bytes());
+            Ok((prefix, data))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Encode extended key with version bytes of prefix
+fn encode(mut data: [u8; 78], version: u32) -> String {
+    data[..4].copy_from_slice(&version.to_be_bytes());
+    bitcoin::base58::encode_check(&data)
+}
+
+/// Extended public key of SLIP-132 prefix
+///
+/// # Examples
+/// ```
+/// use artimonist::{ScriptType, Slip132, Slip132Pub};
+/// # use std::str::FromStr;
+///
+/// let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+/// let key = Slip132Pub::from_str(zpub)?;
+/// assert_eq!(key.prefix.script_type(), ScriptType::P2wpkh);
+/// assert_eq!(key.with_prefix(Slip132::X).to_string(), key.xpub.to_string());
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slip132Pub {
+    /// Extended public key
+    pub xpub: Xpub,
+    /// Prefix
+    pub prefix: Slip132,
+}
+
+impl Slip132Pub {
+    /// Extended public key with prefix
+    pub fn new(xpub: Xpub, prefix: Slip132) -> Self {
+        Slip132Pub { xpub, prefix }
+    }
+
+    /// Convert to other prefix
+    pub fn with_prefix(self, prefix: Slip132) -> Self {
+        Slip132Pub { prefix, ..self }
+    }
+}
+
+impl FromStr for Slip132Pub {
+    type Err = DeriveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, data) = decode(s, false)?;
+        Ok(Slip132Pub::new(Xpub::decode(&data)?, prefix))
+    }
+}
+
+impl std::fmt::Display for Slip132Pub {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let version = self.prefix.version(self.xpub.network, false);
+        write!(f, "{}", encode(self.xpub.encode(), version))
+    }
+}
+
+/// Extended private key of SLIP-132 prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slip132Priv {
+    /// Extended private key
+    pub xpriv: Xpriv,
+    /// Prefix
+    pub prefix: Slip132,
+}
+
+impl Slip132Priv {
+    /// Extended private key with prefix
+    pub fn new(xpriv: Xpriv, prefix: Slip132) -> Self {
+        Slip132Priv { xpriv, prefix }
+    }
+
+    /// Convert to other prefix
+    pub fn with_prefix(self, prefix: Slip132) -> Self {
+        Slip132Priv { prefix, ..self }
+    }
+
+    /// Extended public key of same prefix
+    pub fn to_pub(&self) -> Slip132Pub {
+        let secp = bitcoin::key::Secp256k1::signing_only();
+        Slip132Pub::new(Xpub::from_priv(&secp, &self.xpriv), self.prefix)
+    }
+}
+
+impl FromStr for Slip132Priv {
+    type Err = DeriveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, data) = decode(s, true)?;
+        Ok(Slip132Priv::new(Xpriv::decode(&data)?, prefix))
+    }
+}
+
+impl std::fmt::Display for Slip132Priv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let version = self.prefix.version(self.xpriv.network, true);
+        write!(f, "{}", encode(self.xpriv.encode(), version))
+    }
+}
+
+#[cfg(test)]
+mod slip132_test {
+    use super::*;
+
+    /// SLIP-132 test vectors, mnemonic "abandon ... about", account 0 of BIP44, BIP49, BIP84
+    const VECTORS: [(Slip132, &str, &str); 3] = [
+        (
+            Slip132::X,
+            "xprv9xpXFhFpqdQK3TmytPBqXtGSwS3DLjojFhTGht8gwAAii8py5X6pxeBnQ6ehJiyJ6nDjWGJfZ95WxByFXVkDxHXrqu53WCRGypk2ttuqncb",
+            "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj",
+        ),
+        (
+            Slip132::Y,
+            "yprvAHwhK6RbpuS3dgCYHM5jc2ZvEKd7Bi61u9FVhYMpgMSuZS613T1xxQeKTffhrHY79hZ5PsskBjcc6C2V7DrnsMsNaGDaWev3GLRQRgV7hxF",
+            "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP",
+        ),
+        (
+            Slip132::Z,
+            "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE",
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs",
+        ),
+    ];
+
+    #[test]
+    fn test_slip132() {
+        let standard = VECTORS.map(|(_, xprv, _)| Slip132Priv::from_str(xprv).unwrap().xpriv);
+        for (i, (prefix, xprv, xpub)) in VECTORS.into_iter().enumerate() {
+            let key = Slip132Priv::from_str(xprv).unwrap();
+            assert_eq!(key.prefix, prefix);
+            assert_eq!(key.to_string(), xprv);
+            assert_eq!(key.to_pub().to_string(), xpub);
+            assert_eq!(Slip132Pub::from_str(xpub).unwrap(), key.to_pub());
+
+            let x = key.with_prefix(Slip132::X);
+            assert_eq!(x.to_string(), standard[i].to_string());
+            assert_eq!(x.with_prefix(prefix).to_string(), xprv);
+        }
+
+        // multisig prefixes round trip, testnet prefixes
+        let key = Slip132Pub::from_str(VECTORS[2].2).unwrap();
+        for (prefix, head) in [(Slip132::MultiY, "Ypub"), (Slip132::MultiZ, "Zpub")] {
+            let s = key.with_prefix(prefix).to_string();
+            assert!(s.starts_with(head));
+            assert_eq!(Slip132Pub::from_str(&s).unwrap(), key.with_prefix(prefix));
+        }
+        let mut test = key;
+        test.xpub.network = NetworkKind::Test;
+        assert!(test.to_string().starts_with("vpub"));
+        assert!(
+            test.with_prefix(Slip132::MultiZ)
+                .to_string()
+                .starts_with("Vpub")
+        );
+
+        // private and public keys are not mixed
+        assert!(Slip132Pub::from_str(VECTORS[0].1).is_err());
+        assert!(Slip132Priv::from_str(VECTORS[0].2).is_err());
+        assert_eq!(key.prefix.script_type(), ScriptType::P2wpkh);
+    }
+}
@@ -0,0 +1,215 @@
+/*!
+ * # Descriptions
+ *
+ * [1] - Watch-only derivation
+ *      addresses are derived from account extended public key: `xpub/change/index`,
+ *      same addresses as `bip44_wallet`, `bip49_wallet`, `bip84_wallet` and `bip86_wallet`.
+ *
+ * [2] - Account keys
+ *      exported account keys are accepted: xpub, ypub, zpub, and tpub, upub, vpub of test networks.
+ *      network of addresses is inferred from key, test keys are testnet.
+ *      `*_in` variants take the network, e.g. regtest or signet of test keys.
+ *
+ * [3] - Multisig
+ *      account keys of cosigners, in any order, sorted multisig script of derived keys.
+ *      same addresses as `*_multisig` and `*_wsh_multisig` helpers.
+**/
+use crate::derive::{DeriveError, multisig_script};
+use crate::slip132::Slip132Pub;
+use bitcoin::{
+    Address, CompressedPublicKey, Network, NetworkKind, PublicKey, ScriptBuf,
+    bip32::{ChildNumber, Xpub},
+    key::Secp256k1,
+};
+
+type DeriveResult = Result<(String, String), DeriveError>;
+
+/// Watch-only derivation from account extended public key
+///
+/// # Examples
+/// ```
+/// use artimonist::{BIP84, WatchOnly, Xpriv, Xpub};
+/// # use std::str::FromStr;
+///
+/// let master = Xpriv::from_str("xprv9s21ZrQH143K2sW69WDMTge7PMoK1bfeMy3cpNJxfSkqpPsU7DeHZmth8Sw7DVV2AMbC4jR3fKKgDEPJNNvsqhgTfyZwmWj439MWXUW5U5K")?;
+/// let (zpub, _) = master.bip84_account(0)?;
+///
+/// let account = Xpub::parse_account(&zpub)?;
+/// assert_eq!(account.p2wpkh(3, false)?, master.bip84_wallet(0, 3, false)?.0);
+/// # Ok::<(), artimonist::Error>(())
+/// ```
+pub trait WatchOnly: Sized {
+    /// Parse account extended public key
+    /// # Parameters
+    ///   xpub, ypub, zpub, or tpub, upub, vpub of test networks, any SLIP-132 prefix
+    fn parse_account(s: &str) -> Result<Self, DeriveError>;
+
+    /// Derive a public key of account
+    /// # Derivation path
+    ///   xpub/0/index, xpub/1/index for change
+    fn derive_key(&self, index: u32, change: bool) -> Result<CompressedPublicKey, DeriveError>;
+
+    /// Network of addresses, test keys are testnet
+    fn address_network(&self) -> Network;
+
+    /// Derive a p2pkh address, same as `bip44_wallet`
+    fn p2pkh(&self, index: u32, change: bool) -> Result<String, DeriveError> {
+        self.p2pkh_in(index, change, self.address_network())
+    }
+
+    /// [`p2pkh`](Self::p2pkh) of network
+    fn p2pkh_in(&self, index: u32, change: bool, network: Network) -> Result<String, DeriveError> {
+        let key = self.derive_key(index, change)?;
+        Ok(Address::p2pkh(key, network).to_string())
+    }
+
+    /// Derive a p2sh-p2wpkh address, same as `bip49_wallet`
+    fn p2shwpkh(&self, index: u32, change: bool) -> Result<String, DeriveError> {
+        self.p2shwpkh_in(index, change, self.address_network())
+    }
+
+    /// [`p2shwpkh`](Self::p2shwpkh) of network
+    fn p2shwpkh_in(
+        &self,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> Result<String, DeriveError> {
+        let key = self.derive_key(index, change)?;
+        Ok(Address::p2shwpkh(&key, network).to_string())
+    }
+
+    /// Derive a p2wpkh address, same as `bip84_wallet`
+    fn p2wpkh(&self, index: u32, change: bool) -> Result<String, DeriveError> {
+        self.p2wpkh_in(index, change, self.address_network())
+    }
+
+    /// [`p2wpkh`](Self::p2wpkh) of network
+    fn p2wpkh_in(&self, index: u32, change: bool, network: Network) -> Result<String, DeriveError> {
+        let key = self.derive_key(index, change)?;
+        Ok(Address::p2wpkh(&key, network).to_string())
+    }
+
+    /// Derive a key-path only p2tr address, same as `bip86_wallet`
+    fn p2tr(&self, index: u32, change: bool) -> Result<String, DeriveError> {
+        self.p2tr_in(index, change, self.address_network())
+    }
+
+    /// [`p2tr`](Self::p2tr) of network
+    fn p2tr_in(&self, index: u32, change: bool, network: Network) -> Result<String, DeriveError> {
+        let key = self.derive_key(index, change)?.0.x_only_public_key().0;
+        let secp = Secp256k1::verification_only();
+        Ok(Address::p2tr(&secp, key, None, network).to_string())
+    }
+}
+
+impl WatchOnly for Xpub {
+    fn parse_account(s: &str) -> Result<Self, DeriveError> {
+        Ok(s.parse::<Slip132Pub>()?.xpub)
+    }
+
+    fn derive_key(&self, index: u32, change: bool) -> Result<CompressedPublicKey, DeriveError> {
+        let secp = Secp256k1::verification_only();
+        let path = [
+            ChildNumber::from_normal_idx(change as u32)?,
+            ChildNumber::from_normal_idx(index)?,
+        ];
+        Ok(self.derive_pub(&secp, &path)?.to_pub())
+    }
+
+    #[inline]
+    fn address_network(&self) -> Network {
+        match self.network {
+            NetworkKind::Main => Network::Bitcoin,
+            NetworkKind::Test => Network::Testnet,
+        }
+    }
+}
+
+/// Watch-only multisig from account extended public keys of cosigners
+pub trait WatchMultisig {
+    /// Sorted multisig script of derived keys
+    fn multisig<const M: u8>(&self, index: u32, change: bool) -> Result<ScriptBuf, DeriveError>;
+
+    /// Network of addresses, test keys are testnet
+    fn address_network(&self) -> Network;
+
+    /// Derive a legacy p2sh multisig, same as `bip44_multisig`, `bip49_multisig` and `bip84_multisig`
+    /// # Returns
+    ///   (address, redeem_script)
+    fn p2sh_multisig<const M: u8>(&self, index: u32, change: bool) -> DeriveResult {
+        self.p2sh_multisig_in::<M>(index, change, self.address_network())
+    }
+
+    /// [`p2sh_multisig`](Self::p2sh_multisig) of network
+    fn p2sh_multisig_in<const M: u8>(
+        &self,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> DeriveResult {
+        let script = self.multisig::<M>(index, change)?;
+        let address = Address::p2sh(&script, network)?;
+        Ok((address.to_string(), script.to_hex_string()))
+    }
+
+    /// Derive a p2sh-p2wsh multisig, same as `bip49_wsh_multisig`
+    /// # Returns
+    ///   (address, witness_script)
+    fn p2shwsh_multisig<const M: u8>(&self, index: u32, change: bool) -> DeriveResult {
+        self.p2shwsh_multisig_in::<M>(index, change, self.address_network())
+    }
+
+    /// [`p2shwsh_multisig`](Self::p2shwsh_multisig) of network
+    fn p2shwsh_multisig_in<const M: u8>(
+        &self,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> DeriveResult {
+        let script = self.multisig::<M>(index, change)?;
+        let address = Address::p2shwsh(&script, network);
+        Ok((address.to_string(), script.to_hex_string()))
+    }
+
+    /// Derive a p2wsh multisig, same as `bip84_wsh_multisig`
+    /// # Returns
+    ///   (address, witness_script)
+    fn p2wsh_multisig<const M: u8>(&self, index: u32, change: bool) -> DeriveResult {
+        self.p2wsh_multisig_in::<M>(index, change, self.address_network())
+    }
+
+    /// [`p2wsh_multisig`](Self::p2wsh_multisig) of network
+    fn p2wsh_multisig_in<const M: u8>(
+        &self,
+        index: u32,
+        change: bool,
+        network: Network,
+    ) -> DeriveResult {
+        let script = self.multisig::<M>(index, change)?;
+        let address = Address::p2wsh(&script, network);
+        Ok((address.to_string(), script.to_hex_string()))
+    }
+}
+
+impl WatchMultisig for [Xpub] {
+    fn multisig<const M: u8>(&self, index: u32, change: bool) -> Result<ScriptBuf, DeriveError> {
+        if M < 1 || M as usize > self.len() || self.len() > 15 {
+            return Err(DeriveError::InvalidMultisig("1 <= M <= N <= 15"));
+        }
+        if self.iter().any(|xpub| xpub.network != self[0].network) {
+            return Err(DeriveError::InvalidMultisig("mixed networks"));
+        }
+        let pub_keys = self
+            .iter()
+            .map(|xpub| xpub.derive_key(index, change).map(PublicKey::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(multisig_script(M, pub_keys))
+    }
+
+    /// Network of first cosigner, `DEFAULT_NETWORK` of no cosigners
+    fn address_network(&self) -> Network {
+        self.first()
+            .map_or(crate::DEFAULT_NETWORK, WatchOnly::address_network)
+    }
+}
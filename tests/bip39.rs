@@ -1,4 +1,5 @@
 #![cfg(test)]
+#![allow(clippy::expect_fun_call, clippy::to_string_in_format_args)]
 #![cfg(not(feature = "testnet"))]
 
 use artimonist::Mnemonic;
@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use artimonist::{Xpriv, BIP44};
+use artimonist::{BIP44, Xpriv};
 use bitcoin::hex::FromHex;
 
 #[test]
@@ -21,6 +21,47 @@ fn bip44_derive() {
     });
 }
 
+#[test]
+fn bip44_typed() {
+    use bitcoin::bip32::{DerivationPath, Fingerprint};
+    use std::str::FromStr;
+    use test_data::*;
+    let seed = Vec::from_hex(SEED_HEX).expect("seed");
+    let master = Xpriv::new_master(artimonist::NETWORK, &seed).expect("master");
+    let fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+
+    let account = master.bip44_derive_account(1).expect("account");
+    assert_eq!(account.xpub.to_string(), ACCOUNT_XPUBS[1]);
+    assert_eq!(account.xpriv.to_string(), ACCOUNT_XPRIVS[1]);
+    assert_eq!(account.origin.0, fingerprint);
+
+    let network = match artimonist::NETWORK {
+        bitcoin::NetworkKind::Main => bitcoin::Network::Bitcoin,
+        bitcoin::NetworkKind::Test => bitcoin::Network::Testnet,
+    };
+    let wallet = master
+        .bip44_derive_wallet(0, 2, false, network)
+        .expect("wallet");
+    let (address, wif) = master.bip44_wallet(0, 2, false).expect("wallet");
+    assert_eq!(wallet.address.to_string(), address);
+    assert_eq!(wallet.private_key.to_wif(), wif);
+    let coin = if network == bitcoin::Network::Bitcoin {
+        0
+    } else {
+        1
+    };
+    let path = DerivationPath::from_str(&format!("m/44'/{coin}'/0'/0/2")).unwrap();
+    assert_eq!(wallet.origin, (fingerprint, path));
+
+    let multisig = master
+        .bip44_derive_multisig::<2, 3>(0, 0)
+        .expect("multisig");
+    let (address, script) = master.bip44_multisig::<2, 3>(0, 0).expect("multisig");
+    assert_eq!(multisig.address.to_string(), address);
+    assert_eq!(multisig.script.to_hex_string(), script);
+    assert_eq!(multisig.origins.len(), 3);
+}
+
 /// # References
 ///  <https://iancoleman.io/bip39/>
 #[cfg(not(feature = "testnet"))]
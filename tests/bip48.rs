@@ -0,0 +1,163 @@
+#![cfg(test)]
+
+use artimonist::{BIP48, Cosigner, Error, MultisigScript, Xpriv};
+use bitcoin::hex::FromHex;
+use std::str::FromStr;
+
+fn masters() -> [Xpriv; 3] {
+    use test_data::*;
+    SEEDS_HEX.map(|seed| {
+        let seed = Vec::from_hex(seed).expect("seed");
+        Xpriv::new_master(artimonist::NETWORK, &seed).expect("master")
+    })
+}
+
+#[test]
+fn bip48_cosigners() -> Result<(), Error> {
+    let [a, b, c] = masters();
+    let coin = if artimonist::NETWORK.is_mainnet() {
+        0
+    } else {
+        1
+    };
+    let script = MultisigScript::P2wsh;
+
+    // cosigners exchange `[fingerprint/path]xpub` with each other
+    let keys = [&a, &b, &c].map(|m| {
+        let account = m.bip48_derive_account(0, script).expect("account");
+        assert_eq!(account.origin.1.to_string(), format!("48'/{coin}'/0'/2'"));
+        Cosigner::from(&account).to_string()
+    });
+    #[cfg(not(feature = "testnet"))]
+    assert_eq!(keys[0], test_data::ACCOUNT_KEY);
+    let cosigners = keys
+        .iter()
+        .map(|key| Cosigner::from_str(key))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let wallet_a = a.bip48_multisig(2, 0, script, &cosigners[1..])?;
+    let wallet_c = c.bip48_multisig(2, 0, script, &cosigners[..2])?;
+    assert_eq!(wallet_a.cosigners.len(), 3);
+    assert_eq!(wallet_a.descriptor(), {
+        let mut wallet = wallet_c.clone();
+        wallet.cosigners.rotate_left(1);
+        wallet.descriptor()
+    });
+    for change in [false, true] {
+        for index in 0..5 {
+            let multisig = wallet_a.derive(index, change)?;
+            assert_eq!(multisig, {
+                let mut multisig = wallet_c.derive(index, change)?;
+                multisig.origins.rotate_left(1);
+                multisig
+            });
+            // same as the keys derived by each cosigner privately
+            let path = format!("m/48'/{coin}'/0'/2'/{}/{index}", change as u8);
+            let pub_keys = [&a, &b, &c].map(|m| {
+                let secp = bitcoin::key::Secp256k1::new();
+                let xpriv = m.derive_priv(
+                    &secp,
+                    &path.parse::<bitcoin::bip32::DerivationPath>().unwrap(),
+                );
+                bitcoin::PublicKey::new(xpriv.unwrap().private_key.public_key(&secp))
+            });
+            assert!(
+                pub_keys
+                    .iter()
+                    .all(|k| multisig.script.to_hex_string().contains(&k.to_string()))
+            );
+            assert_eq!(
+                multisig.address,
+                bitcoin::Address::p2wsh(&multisig.script, wallet_a.network)
+            );
+            assert_eq!(multisig.origins[0].1.to_string(), path[2..]);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn bip48_config() -> Result<(), Error> {
+    let [a, b, _] = masters();
+    let script = MultisigScript::P2shP2wsh;
+    let account = b.bip48_derive_account(1, script)?;
+    let wallet = a.bip48_multisig(1, 1, script, &[Cosigner::from(&account)])?;
+    let own = &wallet.cosigners[0];
+    let (xpub, path) = (own.xpub, own.origin.1.to_string());
+
+    let config = wallet.coldcard_config("artimonist");
+    let expected = format!(
+        "# Coldcard Multisig setup file (created by Artimonist)\n#\nName: artimonist\nPolicy: 1 of 2\nDerivation: m/{path}\nFormat: P2SH-P2WSH\n\n73C5DA0A: {xpub}\n{}: {}\n",
+        account.origin.0.to_string().to_uppercase(),
+        account.xpub
+    );
+    assert_eq!(config, expected);
+
+    let descriptor = wallet.descriptor();
+    assert!(descriptor.starts_with(&format!(
+        "sh(wsh(sortedmulti(1,[73c5da0a/{path}]{xpub}/<0;1>/*,"
+    )));
+    assert!(
+        wallet
+            .derive(0, false)?
+            .address
+            .to_string()
+            .starts_with(['2', '3'])
+    );
+
+    // cosigner of fingerprint only origin
+    let cosigner: Cosigner = format!("[{}]{}", account.origin.0, account.xpub).parse()?;
+    let wallet = a.bip48_multisig(1, 1, script, &[cosigner])?;
+    let config = wallet.coldcard_config("artimonist");
+    assert!(config.contains(&format!("Derivation: m/{path}\n73C5DA0A: ")));
+    assert!(config.contains("Derivation: m\n"));
+    Ok(())
+}
+
+#[test]
+fn bip48_invalid() {
+    use artimonist::MultisigWallet;
+    let [a, b, _] = masters();
+    let script = MultisigScript::P2wsh;
+    let own = Cosigner::from(&a.bip48_derive_account(0, script).unwrap());
+    let other = Cosigner::from(&b.bip48_derive_account(0, script).unwrap());
+    let network = wallet_network(&a);
+
+    assert!(
+        a.bip48_multisig(3, 0, script, std::slice::from_ref(&other))
+            .is_err()
+    );
+    assert!(
+        a.bip48_multisig(0, 0, script, std::slice::from_ref(&other))
+            .is_err()
+    );
+    assert!(
+        a.bip48_multisig(1, 0, script, std::slice::from_ref(&own))
+            .is_err()
+    );
+    assert!(MultisigWallet::new(1, script, vec![own.clone(), other.clone()], network).is_ok());
+
+    let foreign = match network {
+        bitcoin::Network::Bitcoin => bitcoin::Network::Testnet,
+        _ => bitcoin::Network::Bitcoin,
+    };
+    assert!(MultisigWallet::new(1, script, vec![own, other], foreign).is_err());
+    assert!(Cosigner::from_str("xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V").is_err());
+}
+
+fn wallet_network(master: &Xpriv) -> bitcoin::Network {
+    match master.network {
+        bitcoin::NetworkKind::Main => bitcoin::Network::Bitcoin,
+        bitcoin::NetworkKind::Test => bitcoin::Network::Testnet,
+    }
+}
+
+mod test_data {
+    pub const SEEDS_HEX: [&str; 3] = [
+        "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4",
+        "000102030405060708090a0b0c0d0e0f",
+        "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+    ];
+    #[cfg(not(feature = "testnet"))]
+    pub const ACCOUNT_KEY: &str = "[73c5da0a/48'/0'/0'/2']xpub6DkFAXWQ2dHxq2vatrt9qyA3bXYU4ToWQwCHbf5XB2mSTexcHZCeKS1VZYcPoBd5X8yVcbXFHJR9R8UCVpt82VX1VhR28mCyxUFL4r6KFrf";
+}
@@ -1,4 +1,5 @@
 #![cfg(test)]
+#![allow(clippy::needless_range_loop)]
 #![cfg(feature = "extfmt")]
 
 use artimonist::{Xpriv, BIP49};
@@ -1,4 +1,5 @@
 #![cfg(test)]
+#![allow(clippy::into_iter_on_ref)]
 #![cfg(not(feature = "testnet"))]
 
 use artimonist::Language::English;
@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+use artimonist::{BIP86, Xpriv};
+use bitcoin::hex::FromHex;
+
+#[cfg(not(feature = "testnet"))]
+#[test]
+fn bip86_derive() {
+    use test_data::*;
+    let seed = Vec::from_hex(SEED_HEX).expect("seed");
+    let master = Xpriv::new_master(artimonist::NETWORK, &seed).expect("master");
+    assert_eq!(master.to_string(), MASTER_KEY);
+
+    let (xpub, xpriv) = master.bip86_account(0).expect("account");
+    assert_eq!(xpub, ACCOUNT_XPUB);
+    assert_eq!(xpriv, ACCOUNT_XPRIV);
+
+    for (index, expected) in (0..).zip(RECEIVE_ADDRESSES) {
+        let (address, _) = master.bip86_wallet(0, index, false).expect("wallet");
+        assert_eq!(address, expected);
+    }
+    let (address, _) = master.bip86_wallet(0, 0, true).expect("wallet");
+    assert_eq!(address, CHANGE_ADDRESS);
+}
+
+#[test]
+fn bip86_typed() {
+    use test_data::*;
+    let seed = Vec::from_hex(SEED_HEX).expect("seed");
+    let master = Xpriv::new_master(artimonist::NETWORK, &seed).expect("master");
+    let network = match artimonist::NETWORK {
+        bitcoin::NetworkKind::Main => bitcoin::Network::Bitcoin,
+        bitcoin::NetworkKind::Test => bitcoin::Network::Testnet,
+    };
+    let coin = if artimonist::NETWORK.is_mainnet() {
+        0
+    } else {
+        1
+    };
+
+    let wallet = master
+        .bip86_derive_wallet(0, 1, false, network)
+        .expect("wallet");
+    assert_eq!(wallet.origin.1.to_string(), format!("86'/{coin}'/0'/0/1"));
+    assert_eq!(
+        wallet.address.address_type(),
+        Some(bitcoin::AddressType::P2tr)
+    );
+    let (address, wif) = master.bip86_wallet(0, 1, false).expect("wallet");
+    assert_eq!(wallet.address.to_string(), address);
+    assert_eq!(wallet.private_key.to_wif(), wif);
+}
+
+/// Test vectors from [BIP86](https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki#test-vectors)
+mod test_data {
+    pub const SEED_HEX: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+    #[cfg(not(feature = "testnet"))]
+    pub const MASTER_KEY: &str = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+    #[cfg(not(feature = "testnet"))]
+    pub const ACCOUNT_XPRIV: &str = "xprv9xgqHN7yz9MwCkxsBPN5qetuNdQSUttZNKw1dcYTV4mkaAFiBVGQziHs3NRSWMkCzvgjEe3n9xV8oYywvM8at9yRqyaZVz6TYYhX98VjsUk";
+    #[cfg(not(feature = "testnet"))]
+    pub const ACCOUNT_XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+    #[cfg(not(feature = "testnet"))]
+    pub const RECEIVE_ADDRESSES: [&str; 2] = [
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+        "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+    ];
+    #[cfg(not(feature = "testnet"))]
+    pub const CHANGE_ADDRESS: &str =
+        "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7";
+}
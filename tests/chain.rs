@@ -0,0 +1,105 @@
+#![cfg(test)]
+
+use artimonist::{
+    AddressChain, BIP44, BIP49, BIP84, BIP86, Error, ScriptType, WatchOnly, Xpriv, Xpub,
+};
+use bitcoin::hex::FromHex;
+
+fn master() -> Xpriv {
+    let seed = Vec::from_hex(test_data::SEED_HEX).expect("seed");
+    Xpriv::new_master(artimonist::NETWORK, &seed).expect("master")
+}
+
+#[test]
+fn chain_range() -> Result<(), Error> {
+    let master = master();
+    for change in [false, true] {
+        let chains = [
+            master.bip44_chain(2, change)?,
+            master.bip49_chain(2, change)?,
+            master.bip84_chain(2, change)?,
+            master.bip86_chain(2, change)?,
+        ];
+        for (i, chain) in chains.iter().enumerate() {
+            let addresses = chain.range(5..15).collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(addresses.len(), 10);
+            for (index, address) in addresses {
+                let wallet = match i {
+                    0 => master.bip44_wallet(2, index, change)?,
+                    1 => master.bip49_wallet(2, index, change)?,
+                    2 => master.bip84_wallet(2, index, change)?,
+                    _ => master.bip86_wallet(2, index, change)?,
+                };
+                assert_eq!(address.to_string(), wallet.0);
+            }
+        }
+    }
+
+    // watch-only chain of exported account
+    let account = Xpub::parse_account(&master.bip84_account(2)?.0)?;
+    let chain = AddressChain::new(
+        &account,
+        true,
+        ScriptType::P2wpkh,
+        account.address_network(),
+    )?;
+    assert_eq!(
+        chain.address(7)?.to_string(),
+        master.bip84_wallet(2, 7, true)?.0
+    );
+    assert!(
+        AddressChain::new(
+            &account,
+            false,
+            ScriptType::P2wsh,
+            bitcoin::Network::Bitcoin
+        )
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn chain_scan() -> Result<(), Error> {
+    let chain = master().bip84_chain(0, false)?;
+    let used = [0, 3, 24].map(|i| chain.address(i).unwrap());
+
+    let mut calls = 0;
+    let found = chain.scan(20, |address| {
+        calls += 1;
+        used.contains(address)
+    })?;
+    assert_eq!(found.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [0, 3]);
+    assert_eq!(calls, 4 + 20);
+
+    let found = chain.scan(25, |address| used.contains(address))?;
+    assert_eq!(
+        found.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+        [0, 3, 24]
+    );
+    assert_eq!(found[2].1, used[2]);
+
+    assert!(chain.scan(0, |_| true)?.is_empty());
+    Ok(())
+}
+
+#[cfg(not(feature = "testnet"))]
+#[test]
+fn chain_bip86() -> Result<(), Error> {
+    let chain = master().bip86_chain(0, false)?;
+    let addresses = chain
+        .range(0..2)
+        .map(|item| item.map(|(_, address)| address.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(addresses, test_data::BIP86_ADDRESSES);
+    Ok(())
+}
+
+mod test_data {
+    pub const SEED_HEX: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+    #[cfg(not(feature = "testnet"))]
+    pub const BIP86_ADDRESSES: [&str; 2] = [
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+        "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+    ];
+}
@@ -0,0 +1,76 @@
+#![cfg(test)]
+
+use artimonist::{BIP44, BIP49, BIP84, Xpriv};
+use bitcoin::hex::FromHex;
+
+#[cfg(not(feature = "testnet"))]
+#[test]
+fn descriptor_accounts() {
+    use artimonist::BIP86;
+    use test_data::*;
+    let seed = Vec::from_hex(SEED_HEX).expect("seed");
+    let master = Xpriv::new_master(artimonist::NETWORK, &seed).expect("master");
+    assert_eq!(master.bip44_descriptor(0).expect("bip44"), BIP44_DESCRIPTOR);
+    assert_eq!(master.bip49_descriptor(0).expect("bip49"), BIP49_DESCRIPTOR);
+    assert_eq!(master.bip84_descriptor(0).expect("bip84"), BIP84_DESCRIPTOR);
+    assert_eq!(master.bip86_descriptor(0).expect("bip86"), BIP86_DESCRIPTOR);
+    assert_eq!(
+        master
+            .bip49_multisig_descriptor::<2, 3>(0)
+            .expect("multisig"),
+        MULTISIG_DESCRIPTOR
+    );
+}
+
+#[test]
+fn descriptor_keys() {
+    use test_data::*;
+    let seed = Vec::from_hex(SEED_HEX).expect("seed");
+    let master = Xpriv::new_master(artimonist::NETWORK, &seed).expect("master");
+    let coin = if artimonist::NETWORK.is_mainnet() {
+        0
+    } else {
+        1
+    };
+
+    let account = master.bip84_derive_account(3).expect("account");
+    let key = account.descriptor_key();
+    assert_eq!(
+        key,
+        format!("[73c5da0a/84'/{coin}'/3']{}/<0;1>/*", account.xpub)
+    );
+
+    let desc = master
+        .bip44_multisig_descriptor::<2, 3>(1)
+        .expect("multisig");
+    let (body, checksum) = desc.split_once('#').expect("checksum");
+    assert_eq!(checksum.len(), 8);
+    assert!(body.starts_with("sh(sortedmulti(2,"));
+    (1..4).for_each(|i| {
+        let account = master.bip44_derive_account(i).expect("account");
+        assert!(body.contains(&account.descriptor_key()));
+    });
+
+    let desc = master
+        .bip84_wsh_multisig_descriptor::<2, 3>(0)
+        .expect("wsh");
+    assert!(desc.starts_with("wsh(sortedmulti(2,[73c5da0a/84'/"));
+    let desc = master
+        .bip49_wsh_multisig_descriptor::<2, 3>(0)
+        .expect("sh-wsh");
+    assert!(desc.starts_with("sh(wsh(sortedmulti(2,[73c5da0a/49'/"));
+}
+
+mod test_data {
+    pub const SEED_HEX: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+    #[cfg(not(feature = "testnet"))]
+    pub const BIP44_DESCRIPTOR: &str = "pkh([73c5da0a/44'/0'/0']xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj/<0;1>/*)#x4nu8kpc";
+    #[cfg(not(feature = "testnet"))]
+    pub const BIP49_DESCRIPTOR: &str = "sh(wpkh([73c5da0a/49'/0'/0']xpub6C6nQwHaWbSrzs5tZ1q7m5R9cPK9eYpNMFesiXsYrgc1P8bvLLAet9JfHjYXKjToD8cBRswJXXbbFpXgwsswVPAZzKMa1jUp2kVkGVUaJa7/<0;1>/*))#wfg3f37j";
+    #[cfg(not(feature = "testnet"))]
+    pub const BIP84_DESCRIPTOR: &str = "wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/<0;1>/*)#hpg6d6w2";
+    #[cfg(not(feature = "testnet"))]
+    pub const BIP86_DESCRIPTOR: &str = "tr([73c5da0a/86'/0'/0']xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ/<0;1>/*)#kjk9q86c";
+    #[cfg(not(feature = "testnet"))]
+    pub const MULTISIG_DESCRIPTOR: &str = "sh(sortedmulti(2,[73c5da0a/49'/0'/0']xpub6C6nQwHaWbSrzs5tZ1q7m5R9cPK9eYpNMFesiXsYrgc1P8bvLLAet9JfHjYXKjToD8cBRswJXXbbFpXgwsswVPAZzKMa1jUp2kVkGVUaJa7/<0;1>/*,[73c5da0a/49'/0'/1']xpub6C6nQwHaWbSs31DwQnenydXNeCXUqJwa9s8dzkWDTZ8N93drzszUSFQyKN7rgxUu8Gs84k4fprimVGp9qjVjgGttXdddo3Lbt9AXT7ZMfkX/<0;1>/*,[73c5da0a/49'/0'/2']xpub6C6nQwHaWbSs5asGH3mDh9RdPyC4anHeg8gJTehv79qk9Rizoruc7ncJR99rYLM9VJ8hWH3qXjdCYU8C9HYqZCopTv41riHmYayCWxpj5QK/<0;1>/*))#7pzz6w5v";
+}
@@ -6,6 +6,12 @@
  *        https://iancoleman.io/bip39/
  *        https://eurychen.me/tools/mnemonic.html
  */
+#![allow(
+    clippy::items_after_test_module,
+    clippy::large_const_arrays,
+    clippy::needless_borrow,
+    clippy::needless_borrows_for_generic_args
+)]
 use bitcoin::{
     bip32::{self, DerivationPath, Xpriv, Xpub},
     hashes::{sha256, Hash},
@@ -222,6 +222,37 @@ fn multisig35() -> Result<(), Error> {
     Ok(())
 }
 
+/// Witness scripts of segwit multisig are the same sorted scripts as electrum's p2sh multisig.
+/// No electrum or sparrow export of segwit multisig addresses of these masters yet,
+/// so only their script types are checked.
+#[cfg(not(feature = "testnet"))]
+#[test]
+fn multisig_wsh() -> Result<(), Error> {
+    use artimonist::BIP84;
+
+    let master = Xpriv::from_str(test_data_23::MASTER_KEY)?;
+    for (i, script) in test_data_23::MULTISIG_SCRIPTS.iter().enumerate() {
+        let (address, witness_script) = master.bip49_wsh_multisig::<2, 3>(0, i as u32)?;
+        assert_eq!(witness_script, *script);
+        assert!(address.starts_with('3'));
+        assert_ne!(address, test_data_23::MULTISIG_ADDRS[i]);
+    }
+
+    let master = Xpriv::from_str(test_data_35::MASTER)?;
+    for (i, script) in test_data_35::MULTISIG_SCRIPTS.iter().enumerate() {
+        let (address, witness_script) = master.bip49_wsh_multisig::<3, 5>(0, i as u32)?;
+        assert_eq!(witness_script, *script);
+        assert!(address.starts_with('3'));
+        assert_ne!(address, test_data_35::MULTISIG_ADDRS[i]);
+    }
+
+    for i in 0..5 {
+        let (address, _) = master.bip84_wsh_multisig::<3, 5>(0, i)?;
+        assert!(address.starts_with("bc1q") && address.len() == 62);
+    }
+    Ok(())
+}
+
 #[cfg(not(feature = "testnet"))]
 mod test_data_35 {
     pub const MASTER: &str = "xprv9s21ZrQH143K2e11sHded37jHumTRiKqGpauB1McmiBbxnf4ZZ4rgSRjyPWF4A8gbNFkzsPGZybFBKX7y3fRKgtgqeFdontXTtPRLoAygY5";
@@ -0,0 +1,133 @@
+#![cfg(test)]
+
+use artimonist::{BIP44, BIP49, BIP84, BIP85, BIP86, Mnemonic};
+use bitcoin::{Network, NetworkKind};
+
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+#[test]
+fn test_network_master() -> Result<(), artimonist::Error> {
+    let mnemonic: Mnemonic = MNEMONIC.parse()?;
+    let master = mnemonic.to_master_in("", Network::Testnet)?;
+    assert!(master.to_string().starts_with("tprv"));
+    assert_eq!(master.network, NetworkKind::Test);
+
+    // network and coin type are inferred from master
+    let (address, wif) = master.bip84_wallet(0, 0, false)?;
+    assert_eq!(address, "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl");
+    assert!(wif.starts_with('c'));
+    let (address, _) = master.bip84_wallet_in(0, 0, false, Network::Regtest)?;
+    assert!(address.starts_with("bcrt1q6rz28mcfaxtmd6v789l9rrlrusdprr9p"));
+    let (xpub, _) = master.bip44_account(0)?;
+    assert!(xpub.starts_with("tpub"));
+    assert!(master.bip85_wallet(0)?.pk.starts_with('c'));
+
+    // same keys of different networks
+    let mainnet = mnemonic.to_master("")?;
+    #[cfg(not(feature = "testnet"))]
+    assert_eq!(mainnet.network, NetworkKind::Main);
+    let (address, _) = mainnet.bip49_wallet_in(0, 0, false, Network::Testnet)?;
+    assert_eq!(address, master.bip49_wallet(0, 0, false)?.0);
+    let (address, _) = mainnet.bip44_wallet_in(0, 0, false, Network::Signet)?;
+    assert_eq!(address, master.bip44_wallet(0, 0, false)?.0);
+    Ok(())
+}
+
+#[test]
+fn test_network_coin() -> Result<(), artimonist::Error> {
+    let mnemonic: Mnemonic = MNEMONIC.parse()?;
+    let testnet = mnemonic.to_master_in("", Network::Testnet)?;
+    let mainnet = mnemonic.to_master_in("", Network::Bitcoin)?;
+
+    // coin type follows the key, whatever the `testnet` feature is
+    let account = testnet.bip84_derive_account(0)?;
+    assert_eq!(account.origin.1.to_string(), "84'/1'/0'");
+    assert!(account.xpub.to_string().starts_with("tpub"));
+    let account = mainnet.bip84_derive_account(0)?;
+    assert_eq!(account.origin.1.to_string(), "84'/0'/0'");
+    assert!(account.xpub.to_string().starts_with("xpub"));
+
+    // or follows the requested network
+    let account = testnet.bip84_derive_account_in(0, Network::Bitcoin)?;
+    assert_eq!(account, mainnet.bip84_derive_account(0)?);
+    let account = mainnet.bip44_derive_account_in(0, Network::Signet)?;
+    assert_eq!(account.origin.1.to_string(), "44'/1'/0'");
+    assert_eq!(account, testnet.bip44_derive_account(0)?);
+    Ok(())
+}
+
+#[test]
+fn test_network_multisig() -> Result<(), artimonist::Error> {
+    let mnemonic: Mnemonic = MNEMONIC.parse()?;
+    let master = mnemonic.to_master_in("", Network::Bitcoin)?;
+    let testnet = mnemonic.to_master_in("", Network::Testnet)?;
+
+    // same scripts of test networks, addresses of requested network
+    let (address, script) = master.bip84_wsh_multisig_in::<2, 3>(0, 0, Network::Regtest)?;
+    assert!(address.starts_with("bcrt1q"));
+    assert_eq!(script, testnet.bip84_wsh_multisig::<2, 3>(0, 0)?.1);
+    let (address, script) = master.bip49_wsh_multisig_in::<2, 3>(0, 0, Network::Signet)?;
+    assert_eq!(address, testnet.bip49_wsh_multisig::<2, 3>(0, 0)?.0);
+    assert_ne!(script, master.bip49_wsh_multisig::<2, 3>(0, 0)?.1);
+    let (address, _) = master.bip44_multisig_in::<2, 3>(0, 0, Network::Regtest)?;
+    assert!(address.starts_with('2'));
+
+    // descriptors of test keys and coin type
+    let descriptor = master.bip84_wsh_multisig_descriptor_in::<2, 3>(0, Network::Regtest)?;
+    assert_eq!(
+        descriptor,
+        testnet.bip84_wsh_multisig_descriptor::<2, 3>(0)?
+    );
+    assert!(descriptor.contains("/84'/1'/2']tpub"));
+    let descriptor = master.bip86_descriptor_in(0, Network::Signet)?;
+    assert_eq!(descriptor, testnet.bip86_descriptor(0)?);
+    Ok(())
+}
+
+#[test]
+fn test_network_chain() -> Result<(), artimonist::Error> {
+    let mnemonic: Mnemonic = MNEMONIC.parse()?;
+    let master = mnemonic.to_master_in("", Network::Bitcoin)?;
+
+    let chain = master.bip84_chain_in(0, false, Network::Regtest)?;
+    assert_eq!(
+        chain.address(0)?.to_string(),
+        master.bip84_wallet_in(0, 0, false, Network::Regtest)?.0
+    );
+    let chain = master.bip49_chain_in(0, true, Network::Signet)?;
+    assert_eq!(
+        chain.address(3)?.to_string(),
+        master.bip49_wallet_in(0, 3, true, Network::Signet)?.0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_network_diagram() -> Result<(), artimonist::Error> {
+    use artimonist::{CombineDiagram, Combiner, GenericDiagram, KdfParams, SimpleDiagram, Xpriv};
+
+    let params = KdfParams {
+        scrypt_log_n: 10,
+        argon2_m_cost: 256,
+        ..KdfParams::V2
+    };
+    let mut mx = [[None; 7]; 7];
+    mx[3][3] = Some('🍩');
+    let diagram = SimpleDiagram(mx);
+
+    // masters of all networks share the same entropy
+    let entropy = diagram.to_entropy_with(b"salt", &params)?;
+    let master = diagram.to_master_in(b"salt", &params, Network::Regtest)?;
+    assert_eq!(master, Xpriv::new_master(Network::Regtest, &entropy)?);
+    let master =
+        diagram.to_master_with_progress_in(b"salt", &params, Network::Signet, &mut |_, _| true)?;
+    assert_eq!(master, Xpriv::new_master(Network::Signet, &entropy)?);
+
+    let diagrams = [(diagram.clone(), "alice"), (diagram, "bob")];
+    let entropy = diagrams.combine_entropy(Combiner::Xor, &params)?;
+    let master = diagrams.combine_master_in(Combiner::Xor, &params, Network::Testnet)?;
+    assert_eq!(master, Xpriv::new_master(Network::Testnet, &entropy)?);
+    assert_eq!(master.network, NetworkKind::Test);
+    Ok(())
+}
@@ -1,6 +1,7 @@
 /*!
  * Tests BIP 85 algorithm
  */
+#![allow(clippy::redundant_field_names)]
 #![allow(unused)]
 ///
 /// # Reference
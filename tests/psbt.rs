@@ -0,0 +1,306 @@
+#![cfg(test)]
+
+use artimonist::{
+    BIP44, BIP48, BIP49, BIP84, BIP86, BIP174, Cosigner, Error, MultisigScript, PsbtError, Xpriv,
+};
+use bitcoin::hex::FromHex;
+use bitcoin::{
+    Amount, CompressedPublicKey, Network, OutPoint, Psbt, ScriptBuf, Sequence, Transaction, TxIn,
+    TxOut, Txid, Witness,
+    absolute::LockTime,
+    bip32::{DerivationPath, Fingerprint},
+    hashes::Hash,
+    psbt::Input,
+    secp256k1::{Message, Secp256k1, XOnlyPublicKey},
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    transaction::Version,
+};
+
+fn new_master(seed: &str) -> Xpriv {
+    let seed = Vec::from_hex(seed).expect("seed");
+    Xpriv::new_master(artimonist::NETWORK, &seed).expect("master")
+}
+
+fn network() -> Network {
+    match artimonist::NETWORK {
+        bitcoin::NetworkKind::Main => Network::Bitcoin,
+        bitcoin::NetworkKind::Test => Network::Testnet,
+    }
+}
+
+/// Spend utxos of inputs, funded by one transaction
+/// Inputs without `witness_utxo` are legacy, given the funding transaction
+fn create_psbt(spends: Vec<(TxOut, Input)>, foreign: Input) -> Psbt {
+    let funding = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: vec![],
+        output: spends.iter().map(|(txout, _)| txout.clone()).collect(),
+    };
+    let txid = funding.compute_txid();
+    let mut input = (0..spends.len() as u32)
+        .map(|vout| OutPoint { txid, vout })
+        .collect::<Vec<_>>();
+    input.push(OutPoint {
+        txid: Txid::all_zeros(),
+        vout: 0,
+    });
+    let tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: input
+            .into_iter()
+            .map(|previous_output| TxIn {
+                previous_output,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::MAX,
+                witness: Witness::new(),
+            })
+            .collect(),
+        output: vec![TxOut {
+            value: Amount::from_sat(spends.len() as u64 * 10_000),
+            script_pubkey: spends[0].0.script_pubkey.clone(),
+        }],
+    };
+    let mut psbt = Psbt::from_unsigned_tx(tx).expect("psbt");
+    for (i, (_, input)) in spends.into_iter().enumerate() {
+        psbt.inputs[i] = input;
+        if psbt.inputs[i].witness_utxo.is_none() {
+            psbt.inputs[i].non_witness_utxo = Some(funding.clone());
+        }
+    }
+    *psbt.inputs.last_mut().unwrap() = foreign;
+    psbt
+}
+
+#[test]
+fn psbt_sign() -> Result<(), Error> {
+    use test_data::*;
+    let (secp, network, master) = (Secp256k1::new(), network(), new_master(SEED_HEX));
+    let txout = |address: &bitcoin::Address| TxOut {
+        value: Amount::from_sat(20_000),
+        script_pubkey: address.script_pubkey(),
+    };
+    let mut spends = vec![];
+
+    // p2pkh, p2sh-p2wpkh, p2wpkh
+    let wallets = [
+        master.bip44_derive_wallet(0, 0, false, network)?,
+        master.bip49_derive_wallet(0, 1, false, network)?,
+        master.bip84_derive_wallet(0, 2, true, network)?,
+    ];
+    for (i, wallet) in wallets.iter().enumerate() {
+        let public_key = wallet.private_key.public_key(&secp);
+        let mut input = Input::default();
+        input
+            .bip32_derivation
+            .insert(public_key.inner, wallet.origin.clone());
+        if i == 1 {
+            let key = CompressedPublicKey(public_key.inner);
+            input.redeem_script = Some(ScriptBuf::new_p2wpkh(&key.wpubkey_hash()));
+        }
+        if i > 0 {
+            input.witness_utxo = Some(txout(&wallet.address));
+        }
+        spends.push((txout(&wallet.address), input));
+    }
+
+    // p2tr key path
+    let taproot = master.bip86_derive_wallet(0, 0, false, network)?;
+    let internal_key = taproot
+        .private_key
+        .public_key(&secp)
+        .inner
+        .x_only_public_key()
+        .0;
+    let mut input = Input {
+        witness_utxo: Some(txout(&taproot.address)),
+        tap_internal_key: Some(internal_key),
+        ..Default::default()
+    };
+    input
+        .tap_key_origins
+        .insert(internal_key, (vec![], taproot.origin.clone()));
+    spends.push((txout(&taproot.address), input));
+
+    // key origins of multisig, keys of other signers are not derived
+    let multisig_input = |input: &mut Input, origins: &[(Fingerprint, DerivationPath)]| {
+        for origin in origins
+            .iter()
+            .filter(|(fp, _)| *fp == master.fingerprint(&secp))
+        {
+            let xpriv = master.derive_priv(&secp, &origin.1).expect("derive");
+            input
+                .bip32_derivation
+                .insert(xpriv.private_key.public_key(&secp), origin.clone());
+        }
+    };
+
+    // p2wsh multisig
+    let multisig = master.bip84_derive_wsh_multisig::<2, 3>(0, 0)?;
+    let mut input = Input {
+        witness_utxo: Some(txout(&multisig.address)),
+        witness_script: Some(multisig.script.clone()),
+        ..Default::default()
+    };
+    multisig_input(&mut input, &multisig.origins);
+    spends.push((txout(&multisig.address), input));
+
+    // legacy p2sh multisig, signed with non_witness_utxo
+    let legacy = [
+        master.bip44_derive_multisig::<2, 3>(0, 1)?,
+        master.bip49_derive_multisig::<2, 3>(0, 2)?,
+        master.bip84_derive_multisig::<2, 3>(0, 3)?,
+    ];
+    for multisig in &legacy {
+        let mut input = Input {
+            redeem_script: Some(multisig.script.clone()),
+            ..Default::default()
+        };
+        multisig_input(&mut input, &multisig.origins);
+        spends.push((txout(&multisig.address), input));
+    }
+
+    // p2sh-p2wsh multisig
+    let nested = master.bip49_derive_wsh_multisig::<2, 3>(0, 4)?;
+    let mut input = Input {
+        witness_utxo: Some(txout(&nested.address)),
+        redeem_script: Some(ScriptBuf::new_p2wsh(&nested.script.wscript_hash())),
+        witness_script: Some(nested.script.clone()),
+        ..Default::default()
+    };
+    multisig_input(&mut input, &nested.origins);
+    spends.push((txout(&nested.address), input));
+
+    // BIP48 multisig with a foreign cosigner
+    let foreign_account =
+        new_master(OTHER_SEED_HEX).bip48_derive_account(0, MultisigScript::P2wsh)?;
+    let wallet = master.bip48_multisig(
+        2,
+        0,
+        MultisigScript::P2wsh,
+        &[Cosigner::from(&foreign_account)],
+    )?;
+    let bip48 = wallet.derive(5, false)?;
+    let mut input = Input {
+        witness_utxo: Some(txout(&bip48.address)),
+        witness_script: Some(bip48.script.clone()),
+        ..Default::default()
+    };
+    multisig_input(&mut input, &bip48.origins);
+    let (fp, path) = bip48.origins[1].clone();
+    let foreign_key = new_master(OTHER_SEED_HEX)
+        .derive_priv(&secp, &path)?
+        .private_key
+        .public_key(&secp);
+    input.bip32_derivation.insert(foreign_key, (fp, path));
+    spends.push((txout(&bip48.address), input));
+
+    // input of other signer
+    let other = new_master(OTHER_SEED_HEX).bip84_derive_wallet(0, 0, false, network)?;
+    let mut foreign = Input {
+        witness_utxo: Some(txout(&other.address)),
+        ..Default::default()
+    };
+    let other_key = other.private_key.public_key(&secp).inner;
+    foreign
+        .bip32_derivation
+        .insert(other_key, other.origin.clone());
+
+    let prevouts = spends
+        .iter()
+        .map(|(txout, _)| txout.clone())
+        .chain([txout(&other.address)])
+        .collect::<Vec<_>>();
+    let psbt = master.bip174_sign(create_psbt(spends, foreign))?;
+
+    // ecdsa signatures
+    let mut cache = SighashCache::new(&psbt.unsigned_tx);
+    for (i, count) in [
+        (0, 1),
+        (1, 1),
+        (2, 1),
+        (4, 3),
+        (5, 3),
+        (6, 3),
+        (7, 3),
+        (8, 3),
+        (9, 1),
+    ] {
+        let (msg, _) = psbt.sighash_ecdsa(i, &mut cache).expect("sighash");
+        let sigs = &psbt.inputs[i].partial_sigs;
+        assert_eq!(sigs.len(), count);
+        for (pk, sig) in sigs {
+            assert!(secp.verify_ecdsa(&msg, &sig.signature, &pk.inner).is_ok());
+        }
+    }
+    // schnorr signature of tweaked key
+    let sighash = cache
+        .taproot_key_spend_signature_hash(3, &Prevouts::All(&prevouts), TapSighashType::Default)
+        .expect("sighash");
+    let msg = Message::from_digest(sighash.to_byte_array());
+    let output_key = XOnlyPublicKey::from_slice(&taproot.address.script_pubkey().as_bytes()[2..])
+        .expect("output key");
+    let sig = psbt.inputs[3].tap_key_sig.expect("tap key sig");
+    assert!(
+        secp.verify_schnorr(&sig.signature, &msg, &output_key)
+            .is_ok()
+    );
+
+    // own key of BIP48 multisig only
+    let own_key = master
+        .derive_priv(&secp, &bip48.origins[0].1)?
+        .private_key
+        .public_key(&secp);
+    assert!(
+        psbt.inputs[9]
+            .partial_sigs
+            .contains_key(&bitcoin::PublicKey::new(own_key))
+    );
+
+    assert!(psbt.inputs[10].partial_sigs.is_empty());
+    Ok(())
+}
+
+#[test]
+fn psbt_skip() -> Result<(), Error> {
+    use test_data::*;
+    let (secp, network, master) = (Secp256k1::new(), network(), new_master(SEED_HEX));
+    let wallet = master.bip84_derive_wallet(1, 0, false, network)?;
+    let txout = TxOut {
+        value: Amount::from_sat(20_000),
+        script_pubkey: wallet.address.script_pubkey(),
+    };
+    let mut input = Input {
+        witness_utxo: Some(txout.clone()),
+        ..Default::default()
+    };
+    let public_key = wallet.private_key.public_key(&secp).inner;
+    input
+        .bip32_derivation
+        .insert(public_key, wallet.origin.clone());
+
+    // input of other signer without utxo can't be signed, but skipped
+    let mut foreign = Input::default();
+    let path = DerivationPath::from(vec![]);
+    foreign.bip32_derivation.insert(
+        public_key,
+        (Fingerprint::from([0xde, 0xad, 0xbe, 0xef]), path),
+    );
+    let psbt = create_psbt(vec![(txout, input)], foreign);
+
+    let other = new_master(OTHER_SEED_HEX);
+    assert!(matches!(
+        other.bip174_sign(psbt.clone()),
+        Err(PsbtError::NoMatchingInput(_))
+    ));
+    let signed = master.bip174_sign(psbt)?;
+    assert_eq!(signed.inputs[0].partial_sigs.len(), 1);
+    assert!(signed.inputs[1].partial_sigs.is_empty());
+    Ok(())
+}
+
+mod test_data {
+    pub const SEED_HEX: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+    pub const OTHER_SEED_HEX: &str = "000102030405060708090a0b0c0d0e0f";
+}
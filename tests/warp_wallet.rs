@@ -33,6 +33,7 @@
  *  cargo test --release
  *  ```
  */
+#![allow(clippy::assign_op_pattern, clippy::clone_on_copy, clippy::tabs_in_doc_comments)]
 use bitcoin::{
     base58,
     hashes::{sha256, Hash},
@@ -0,0 +1,148 @@
+#![cfg(test)]
+
+use artimonist::{BIP44, BIP49, BIP84, BIP86, Error, WatchMultisig, WatchOnly, Xpriv, Xpub};
+use bitcoin::hex::FromHex;
+
+fn master() -> Xpriv {
+    let seed = Vec::from_hex(test_data::SEED_HEX).expect("seed");
+    Xpriv::new_master(artimonist::NETWORK, &seed).expect("master")
+}
+
+#[test]
+fn watch_wallets() -> Result<(), Error> {
+    let master = master();
+    let bip44 = Xpub::parse_account(&master.bip44_account(1)?.0)?;
+    let bip49 = Xpub::parse_account(&master.bip49_account(1)?.0)?;
+    let bip84 = Xpub::parse_account(&master.bip84_account(1)?.0)?;
+    let bip86 = Xpub::parse_account(&master.bip86_account(1)?.0)?;
+    assert_eq!(bip49, master.bip49_derive_account(1)?.xpub);
+    assert_eq!(bip84, master.bip84_derive_account(1)?.xpub);
+
+    for change in [false, true] {
+        for index in 0..5 {
+            let wallet = |w: (String, String)| w.0;
+            assert_eq!(
+                bip44.p2pkh(index, change)?,
+                wallet(master.bip44_wallet(1, index, change)?)
+            );
+            assert_eq!(
+                bip49.p2shwpkh(index, change)?,
+                wallet(master.bip49_wallet(1, index, change)?)
+            );
+            assert_eq!(
+                bip84.p2wpkh(index, change)?,
+                wallet(master.bip84_wallet(1, index, change)?)
+            );
+            assert_eq!(
+                bip86.p2tr(index, change)?,
+                wallet(master.bip86_wallet(1, index, change)?)
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn watch_multisig() -> Result<(), Error> {
+    let master = master();
+    let mut bip49 = (2..5)
+        .map(|i| Xpub::parse_account(&master.bip49_account(i)?.0))
+        .collect::<Result<Vec<_>, _>>()?;
+    let bip84 = (2..5)
+        .map(|i| Xpub::parse_account(&master.bip84_account(i)?.0))
+        .collect::<Result<Vec<_>, _>>()?;
+    // cosigners in any order
+    bip49.reverse();
+
+    for index in 0..5 {
+        let legacy = master.bip49_multisig::<2, 3>(2, index)?;
+        assert_eq!(bip49.p2sh_multisig::<2>(index, false)?, legacy);
+        let nested = master.bip49_wsh_multisig::<2, 3>(2, index)?;
+        assert_eq!(bip49.p2shwsh_multisig::<2>(index, false)?, nested);
+        let native = master.bip84_wsh_multisig::<2, 3>(2, index)?;
+        assert_eq!(bip84.p2wsh_multisig::<2>(index, false)?, native);
+    }
+    assert!(bip84.p2wsh_multisig::<4>(0, false).is_err());
+    assert!(bip84[..0].p2wsh_multisig::<0>(0, false).is_err());
+    Ok(())
+}
+
+#[test]
+fn watch_network() -> Result<(), Error> {
+    use bitcoin::{Network, NetworkKind};
+    let seed = Vec::from_hex(test_data::SEED_HEX).expect("seed");
+    let master = Xpriv::new_master(NetworkKind::Test, &seed)?;
+    let bip44 = master.bip44_derive_account(1)?.xpub;
+    let bip84 = master.bip84_derive_account(1)?.xpub;
+    assert_eq!(bip84.address_network(), Network::Testnet);
+
+    // regtest addresses of test keys
+    let regtest = Network::Regtest;
+    let (address, _) = master.bip84_wallet_in(1, 0, false, regtest)?;
+    assert_eq!(bip84.p2wpkh_in(0, false, regtest)?, address);
+    assert!(address.starts_with("bcrt1"));
+    assert_ne!(bip84.p2wpkh(0, false)?, address);
+    let (address, _) = master.bip44_wallet_in(1, 3, true, Network::Signet)?;
+    assert_eq!(bip44.p2pkh_in(3, true, Network::Signet)?, address);
+
+    let cosigners = (2..5)
+        .map(|i| Ok(master.bip84_derive_account(i)?.xpub))
+        .collect::<Result<Vec<_>, Error>>()?;
+    assert_eq!(cosigners.address_network(), Network::Testnet);
+    let native = master.bip84_wsh_multisig_in::<2, 3>(2, 0, regtest)?;
+    assert_eq!(cosigners.p2wsh_multisig_in::<2>(0, false, regtest)?, native);
+    assert!(native.0.starts_with("bcrt1"));
+    assert_ne!(cosigners.p2wsh_multisig::<2>(0, false)?, native);
+    Ok(())
+}
+
+#[test]
+fn watch_parse() -> Result<(), Error> {
+    let master = master();
+    let (xpub, xpriv) = master.bip84_account(0)?;
+    assert!(Xpub::parse_account(&xpriv).is_err());
+    assert!(Xpub::parse_account(&xpub[1..]).is_err());
+    assert!(Xpub::parse_account("").is_err());
+
+    #[cfg(not(feature = "testnet"))]
+    for (s, expected) in test_data::ACCOUNT_KEYS {
+        assert_eq!(Xpub::parse_account(s)?.to_string(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn watch_slip132() -> Result<(), Error> {
+    use artimonist::{ScriptType, Slip132, Slip132Priv, Slip132Pub};
+    use std::str::FromStr;
+    let master = master();
+    let (y, z) = match cfg!(feature = "extfmt") {
+        true => (Slip132::Y, Slip132::Z),
+        false => (Slip132::X, Slip132::X),
+    };
+    for (prefix, (xpub, xpriv)) in [(y, master.bip49_account(0)?), (z, master.bip84_account(0)?)] {
+        let (key, secret) = (Slip132Pub::from_str(&xpub)?, Slip132Priv::from_str(&xpriv)?);
+        assert_eq!((key.prefix, secret.prefix), (prefix, prefix));
+        assert_eq!((key.to_string(), secret.to_string()), (xpub, xpriv));
+        assert_eq!(secret.to_pub(), key);
+    }
+    let zpub = Slip132Pub::from_str(&master.bip84_account(0)?.0)?.with_prefix(Slip132::Z);
+    assert_eq!(zpub.prefix.script_type(), ScriptType::P2wpkh);
+    Ok(())
+}
+
+mod test_data {
+    pub const SEED_HEX: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+    /// BIP49 and BIP84 account 0 of test vectors
+    #[cfg(not(feature = "testnet"))]
+    pub const ACCOUNT_KEYS: [(&str, &str); 2] = [
+        (
+            "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP",
+            "xpub6C6nQwHaWbSrzs5tZ1q7m5R9cPK9eYpNMFesiXsYrgc1P8bvLLAet9JfHjYXKjToD8cBRswJXXbbFpXgwsswVPAZzKMa1jUp2kVkGVUaJa7",
+        ),
+        (
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs",
+            "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V",
+        ),
+    ];
+}